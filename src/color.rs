@@ -11,6 +11,7 @@ use std::fmt;
 use std::str::FromStr;
 
 /// An RGB color which can be serialized into and deserialized from a hexadecimal color string.
+/// It can also be deserialized from an integer like `0xRRGGBB`.
 ///
 /// Example:
 /// ```
@@ -21,16 +22,164 @@ use std::str::FromStr;
 /// assert_eq!(format!("{}", red), "#FF0000");
 /// assert_eq!("#FF0000".parse::<Color>().unwrap(), red);
 /// ```
-#[derive(Default, Debug, PartialEq)]
+#[derive(Default, Debug, PartialEq, Clone, Copy)]
 pub struct Color {
     /// Red
     pub r: u8,
-    /// Blue
-    pub g: u8,
     /// Green
+    pub g: u8,
+    /// Blue
     pub b: u8,
 }
 
+impl Color {
+    /// Creates a color from its red, green and blue channels.
+    pub const fn new(r: u8, g: u8, b: u8) -> Color {
+        Color { r, g, b }
+    }
+
+    /// Returns the red, green and blue channels of the color, in that order.
+    pub const fn rgb(&self) -> (u8, u8, u8) {
+        (self.r, self.g, self.b)
+    }
+
+    /// Returns the color in `palette` that's closest to this one, by Euclidean distance in RGB
+    /// space. If `palette` is empty, this color is returned unchanged.
+    ///
+    /// Example:
+    /// ```
+    /// use octopt::color::Color;
+    ///
+    /// let palette = [Color { r: 0, g: 0, b: 0 }, Color { r: 255, g: 255, b: 255 }];
+    /// let gray = Color { r: 100, g: 100, b: 100 };
+    /// assert_eq!(gray.nearest_in(&palette), Color { r: 0, g: 0, b: 0 });
+    /// ```
+    pub fn nearest_in(&self, palette: &[Color]) -> Color {
+        palette
+            .iter()
+            .min_by_key(|color| self.distance_squared(color))
+            .copied()
+            .unwrap_or(*self)
+    }
+
+    /// Returns the shade of gray with the same luminance as this color, using the ITU-R BT.601
+    /// luma coefficients.
+    pub fn to_grayscale(&self) -> Color {
+        let luma =
+            (0.299 * f32::from(self.r) + 0.587 * f32::from(self.g) + 0.114 * f32::from(self.b))
+                .round() as u8;
+        Color {
+            r: luma,
+            g: luma,
+            b: luma,
+        }
+    }
+
+    /// Linearly interpolates between this color and `other`, one channel at a time, where a `t`
+    /// of 0 returns this color and a `t` of 1 returns `other`. `t` is clamped to between 0 and 1,
+    /// and the channels are rounded to the nearest integer.
+    pub fn lerp(&self, other: &Color, t: f32) -> Color {
+        let t = t.clamp(0.0, 1.0);
+        let lerp = |a: u8, b: u8| (f32::from(a) + (f32::from(b) - f32::from(a)) * t).round() as u8;
+        Color {
+            r: lerp(self.r, other.r),
+            g: lerp(self.g, other.g),
+            b: lerp(self.b, other.b),
+        }
+    }
+
+    /// Returns the fraction of pixels that should be lit, ie. set to `on`, for a dither pattern
+    /// that approximates this color on a display that can only show `on` and `off`. This is the
+    /// `t` for which `off.lerp(on, t)` is closest to this color, by Euclidean distance in RGB
+    /// space, clamped to between 0 and 1. If `on` and `off` are the same color, this is 0.
+    ///
+    /// Example:
+    /// ```
+    /// use octopt::color::Color;
+    ///
+    /// let white = Color { r: 255, g: 255, b: 255 };
+    /// let black = Color { r: 0, g: 0, b: 0 };
+    /// let gray = Color { r: 51, g: 51, b: 51 };
+    /// assert_eq!(gray.to_dither_density(&white, &black), 0.2);
+    /// ```
+    pub fn to_dither_density(&self, on: &Color, off: &Color) -> f32 {
+        let from_off = |color: &Color| {
+            [(color.r, off.r), (color.g, off.g), (color.b, off.b)]
+                .map(|(channel, off)| f32::from(channel) - f32::from(off))
+        };
+        let (this, on) = (from_off(self), from_off(on));
+        let dot: f32 = this.iter().zip(&on).map(|(a, b)| a * b).sum();
+        let length_squared: f32 = on.iter().map(|b| b * b).sum();
+        if length_squared == 0.0 {
+            0.0
+        } else {
+            (dot / length_squared).clamp(0.0, 1.0)
+        }
+    }
+
+    /// Checks whether this color is equal to `other` when each channel may differ by at most
+    /// `tolerance`, for comparing colors with rounding differences after conversions like
+    /// [`Color::lerp`].
+    ///
+    /// Example:
+    /// ```
+    /// use octopt::color::Color;
+    ///
+    /// let color = Color { r: 255, g: 204, b: 0 };
+    /// assert!(color.approx_eq(&Color { r: 254, g: 205, b: 0 }, 1));
+    /// ```
+    pub fn approx_eq(&self, other: &Color, tolerance: u8) -> bool {
+        [(self.r, other.r), (self.g, other.g), (self.b, other.b)]
+            .iter()
+            .all(|(a, b)| a.abs_diff(*b) <= tolerance)
+    }
+
+    /// Returns either black or white, whichever has the higher contrast against this color, for
+    /// text drawn on top of it. Contrast is measured as in the Web Content Accessibility
+    /// Guidelines (WCAG).
+    ///
+    /// Example:
+    /// ```
+    /// use octopt::color::Color;
+    ///
+    /// let yellow = Color { r: 255, g: 204, b: 0 };
+    /// assert_eq!(yellow.readable_on(), Color { r: 0, g: 0, b: 0 });
+    /// ```
+    pub fn readable_on(&self) -> Color {
+        let luminance = self.relative_luminance();
+        // The contrast ratio against black is (L + 0.05) / 0.05 and against white it's
+        // 1.05 / (L + 0.05), so black wins when (L + 0.05)² > 0.05 × 1.05
+        if (luminance + 0.05) * (luminance + 0.05) > 0.05 * 1.05 {
+            Color { r: 0, g: 0, b: 0 }
+        } else {
+            Color {
+                r: 255,
+                g: 255,
+                b: 255,
+            }
+        }
+    }
+
+    fn relative_luminance(&self) -> f32 {
+        let linear = |channel: u8| {
+            let channel = f32::from(channel) / 255.0;
+            if channel <= 0.04045 {
+                channel / 12.92
+            } else {
+                ((channel + 0.055) / 1.055).powf(2.4)
+            }
+        };
+        0.2126 * linear(self.r) + 0.7152 * linear(self.g) + 0.0722 * linear(self.b)
+    }
+
+    fn distance_squared(&self, other: &Color) -> u32 {
+        let dr = u32::from(self.r.abs_diff(other.r));
+        let dg = u32::from(self.g.abs_diff(other.g));
+        let db = u32::from(self.b.abs_diff(other.b));
+        dr * dr + dg * dg + db * db
+    }
+}
+
 impl fmt::Display for Color {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         write!(f, "#{:02X}{:02X}{:02X}", self.r, self.g, self.b)
@@ -55,6 +204,29 @@ impl<'de> Visitor<'de> for ColorVisitor {
         formatter.write_str("a hex RGB number between #000000 and #FFFFFF")
     }
 
+    fn visit_u64<E>(self, value: u64) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        match u32::try_from(value) {
+            Ok(rgb @ 0..=0xFF_FFFF) => {
+                let [_, r, g, b] = rgb.to_be_bytes();
+                Ok(Color { r, g, b })
+            }
+            _ => Err(E::invalid_value(de::Unexpected::Unsigned(value), &self)),
+        }
+    }
+
+    fn visit_i64<E>(self, value: i64) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        match u64::try_from(value) {
+            Ok(value) => self.visit_u64(value),
+            Err(_) => Err(E::invalid_value(de::Unexpected::Signed(value), &self)),
+        }
+    }
+
     fn visit_str<E>(self, value: &str) -> Result<Self::Value, E>
     where
         E: de::Error,
@@ -71,16 +243,30 @@ impl<'de> Deserialize<'de> for Color {
     where
         D: Deserializer<'de>,
     {
-        deserializer.deserialize_str(ColorVisitor)
+        deserializer.deserialize_any(ColorVisitor)
     }
 }
 
+/// Parses a color from a hexadecimal string with or without a leading #, or any CSS color, like
+/// `red` or `rgb(255, 0, 0)`. The alpha channel of CSS colors with transparency, like
+/// `rgba(255, 0, 0, 0.5)`, is ignored.
+///
+/// Surrounding whitespace and a matching pair of surrounding quotes are ignored too, since they
+/// often show up in hand-edited INI files, like `colors.plane1 = "FFCC00"`.
 impl FromStr for Color {
     type Err = ColorParseError;
     fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        let s = ['"', '\'']
+            .iter()
+            .find_map(|&quote| s.strip_prefix(quote)?.strip_suffix(quote))
+            .map_or(s, str::trim);
         let css_color = match CssColor::from_str(s) {
             Ok(css_color) => css_color,
-            Err(_) => CssColor::from_str(&format!("#{}", s))?,
+            Err(_) if s.chars().all(|c| c.is_ascii_hexdigit()) => {
+                CssColor::from_str(&format!("#{}", s))?
+            }
+            Err(e) => return Err(e),
         };
 
         Ok(Color {
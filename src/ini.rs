@@ -1,12 +1,18 @@
 use crate::color::Color;
-use crate::{u8, Colors, Font, LoResDxy0Behavior, Options, Quirks, ScreenRotation, TouchMode};
-use serde::de::{self, Deserializer, Unexpected};
+use crate::{
+    BoolOrMode, BoolOrU8OrStr, ClipMode, Colors, Font, LoResDxy0Behavior, LoadStoreMode, Options,
+    OptionsField, Quirks, ScreenRotation, TouchMode,
+};
+use serde::de::Deserializer;
 use serde::{Deserialize, Serialize, Serializer};
 use serde_repr::{Deserialize_repr, Serialize_repr};
 use serde_with::skip_serializing_none;
 use std::fmt;
 use std::str::FromStr;
 
+// Note that C-Octo's key names don't line up with Octo's JSON names: `colors.plane0` is the color
+// of pixels that are off in both planes (Octo's `backgroundColor`), while `colors.background` is
+// the color of the area around the display when the buzzer is silent (Octo's `quietColor`).
 #[skip_serializing_none]
 #[derive(Debug, PartialEq, Serialize, Deserialize)]
 pub(crate) struct ColorsIni {
@@ -79,13 +85,15 @@ impl From<ColorsIni> for Colors {
             background_color: colors.background_color,
             buzz_color: colors.buzz_color,
             quiet_color: colors.quiet_color,
+            background_transparent: None,
         }
     }
 }
 
-#[derive(Debug, PartialEq, Serialize, Deserialize)]
+#[derive(Debug, Default, PartialEq, Serialize, Deserialize)]
 #[serde(rename_all = "lowercase")]
 pub(crate) enum TouchModeIni {
+    #[default]
     None,
     Swipe,
     Seg16,
@@ -120,73 +128,55 @@ impl From<TouchModeIni> for TouchMode {
     }
 }
 
-impl Default for TouchModeIni {
-    fn default() -> Self {
-        Self::None
-    }
-}
-
-impl Default for ScreenRotationIni {
-    fn default() -> Self {
-        Self::Normal
-    }
-}
-
-impl Default for FontIni {
-    fn default() -> Self {
-        Self::Octo
-    }
-}
-
 #[skip_serializing_none]
 #[derive(Debug, PartialEq, Serialize, Deserialize)]
 pub(crate) struct QuirksIni {
     #[serde(
         rename = "quirks.shift",
-        deserialize_with = "some_bool_from_int",
-        serialize_with = "int_from_some_bool",
+        deserialize_with = "some_mode_from_int",
+        serialize_with = "int_from_some_mode",
         default
     )]
     shift: Option<bool>,
     #[serde(
         rename = "quirks.loadstore",
-        deserialize_with = "some_bool_from_int",
-        serialize_with = "int_from_some_bool",
+        deserialize_with = "some_mode_from_int",
+        serialize_with = "int_from_some_mode",
         default
     )]
-    load_store: Option<bool>,
+    load_store: Option<LoadStoreMode>,
     #[serde(
         rename = "quirks.jump0",
-        deserialize_with = "some_bool_from_int",
-        serialize_with = "int_from_some_bool",
+        deserialize_with = "some_mode_from_int",
+        serialize_with = "int_from_some_mode",
         default
     )]
     jump0: Option<bool>,
     #[serde(
         rename = "quirks.logic",
-        deserialize_with = "some_bool_from_int",
-        serialize_with = "int_from_some_bool",
+        deserialize_with = "some_mode_from_int",
+        serialize_with = "int_from_some_mode",
         default
     )]
     logic: Option<bool>,
     #[serde(
         rename = "quirks.clip",
-        deserialize_with = "some_bool_from_int",
-        serialize_with = "int_from_some_bool",
+        deserialize_with = "some_mode_from_int",
+        serialize_with = "int_from_some_mode",
         default
     )]
-    clip: Option<bool>,
+    clip: Option<ClipMode>,
     #[serde(
         rename = "quirks.vblank",
-        deserialize_with = "some_bool_from_int",
-        serialize_with = "int_from_some_bool",
+        deserialize_with = "some_mode_from_int",
+        serialize_with = "int_from_some_mode",
         default
     )]
     vblank: Option<bool>,
     #[serde(
         rename = "quirks.vforder",
-        deserialize_with = "some_bool_from_int",
-        serialize_with = "int_from_some_bool",
+        deserialize_with = "some_mode_from_int",
+        serialize_with = "int_from_some_mode",
         default
     )]
     vf_order: Option<bool>,
@@ -194,43 +184,43 @@ pub(crate) struct QuirksIni {
     lores_dxy0: Option<LoResDxy0Behavior>,
     #[serde(
         rename = "quirks.resclear",
-        deserialize_with = "some_bool_from_int",
-        serialize_with = "int_from_some_bool",
+        deserialize_with = "some_mode_from_int",
+        serialize_with = "int_from_some_mode",
         default
     )]
     res_clear: Option<bool>,
     #[serde(
         rename = "quirks.delaywrap",
-        deserialize_with = "some_bool_from_int",
-        serialize_with = "int_from_some_bool",
+        deserialize_with = "some_mode_from_int",
+        serialize_with = "int_from_some_mode",
         default
     )]
     delay_wrap: Option<bool>,
     #[serde(
         rename = "quirks.hirescollision",
-        deserialize_with = "some_bool_from_int",
-        serialize_with = "int_from_some_bool",
+        deserialize_with = "some_mode_from_int",
+        serialize_with = "int_from_some_mode",
         default
     )]
     hires_collision: Option<bool>,
     #[serde(
         rename = "quirks.clipcollision",
-        deserialize_with = "some_bool_from_int",
-        serialize_with = "int_from_some_bool",
+        deserialize_with = "some_mode_from_int",
+        serialize_with = "int_from_some_mode",
         default
     )]
     clip_collision: Option<bool>,
     #[serde(
         rename = "quirks.scroll",
-        deserialize_with = "some_bool_from_int",
-        serialize_with = "int_from_some_bool",
+        deserialize_with = "some_mode_from_int",
+        serialize_with = "int_from_some_mode",
         default
     )]
     scroll: Option<bool>,
     #[serde(
         rename = "quirks.overflow_i",
-        deserialize_with = "some_bool_from_int",
-        serialize_with = "int_from_some_bool",
+        deserialize_with = "some_mode_from_int",
+        serialize_with = "int_from_some_mode",
         default
     )]
     overflow_i: Option<bool>,
@@ -283,7 +273,7 @@ impl From<QuirksIni> for Quirks {
 pub(crate) struct OptionsIni {
     #[serde(default, rename = "core.tickrate")]
     tickrate: Option<u16>,
-    #[serde(default, rename = "core.max_rom")]
+    #[serde(default, rename = "core.max_rom", alias = "core.maxSize")]
     max_size: Option<u16>,
     #[serde(default, rename = "core.rotation")]
     screen_rotation: ScreenRotationIni,
@@ -323,6 +313,11 @@ impl From<OptionsIni> for Options {
             max_size: options.max_size,
             screen_rotation: ScreenRotation::from(options.screen_rotation),
             font_style: Font::from(options.font_style),
+            font_big_style: None,
+            plane_count: None,
+            buzzer_pitch: None,
+            font_address: None,
+            custom_font: None,
             touch_input_mode: TouchMode::from(options.touch_input_mode),
             start_address: options.start_address,
             colors: Colors::from(options.colors),
@@ -331,9 +326,51 @@ impl From<OptionsIni> for Options {
     }
 }
 
-#[derive(Serialize_repr, Deserialize_repr, PartialEq, Debug)]
+impl Options {
+    /// Returns the key that the given field has in the INI format used by C-Octo, like
+    /// `core.tickrate` or `colors.plane1`, or `None` if the field can't be stored in that format.
+    pub fn ini_key_for(field: OptionsField) -> Option<&'static str> {
+        Some(match field {
+            OptionsField::Tickrate => "core.tickrate",
+            OptionsField::MaxSize => "core.max_rom",
+            OptionsField::ScreenRotation => "core.rotation",
+            OptionsField::FontStyle => "core.font",
+            OptionsField::TouchInputMode => "core.touch_mode",
+            OptionsField::StartAddress => "core.start_address",
+            OptionsField::FillColor => "colors.plane1",
+            OptionsField::FillColor2 => "colors.plane2",
+            OptionsField::BlendColor => "colors.plane3",
+            OptionsField::BackgroundColor => "colors.plane0",
+            OptionsField::BuzzColor => "colors.sound",
+            OptionsField::QuietColor => "colors.background",
+            OptionsField::Shift => "quirks.shift",
+            OptionsField::LoadStore => "quirks.loadstore",
+            OptionsField::Jump0 => "quirks.jump0",
+            OptionsField::Logic => "quirks.logic",
+            OptionsField::Clip => "quirks.clip",
+            OptionsField::Vblank => "quirks.vblank",
+            OptionsField::VfOrder => "quirks.vforder",
+            OptionsField::LoresDxy0 => "quirks.lores_dxy0",
+            OptionsField::ResClear => "quirks.resclear",
+            OptionsField::DelayWrap => "quirks.delaywrap",
+            OptionsField::HiresCollision => "quirks.hirescollision",
+            OptionsField::ClipCollision => "quirks.clipcollision",
+            OptionsField::Scroll => "quirks.scroll",
+            OptionsField::OverflowI => "quirks.overflow_i",
+            OptionsField::FontBigStyle
+            | OptionsField::PlaneCount
+            | OptionsField::BuzzerPitch
+            | OptionsField::FontAddress
+            | OptionsField::CustomFont
+            | OptionsField::BackgroundTransparent => return None,
+        })
+    }
+}
+
+#[derive(Serialize_repr, Deserialize_repr, PartialEq, Debug, Default)]
 #[repr(u16)]
 pub(crate) enum ScreenRotationIni {
+    #[default]
     Normal = 0,
     ClockWise = 90,
     UpsideDown = 180,
@@ -368,7 +405,7 @@ impl From<ScreenRotationIni> for ScreenRotation {
 impl FromStr for OptionsIni {
     type Err = serde_ini::de::Error;
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        serde_ini::from_str(s)
+        serde_ini::from_str(crate::strip_bom(s))
     }
 }
 
@@ -384,8 +421,109 @@ impl fmt::Display for OptionsIni {
     }
 }
 
-#[derive(Debug, PartialEq, Serialize, Deserialize)]
+/// An INI file that remembers its comments, blank lines and the order of its keys, so it can be
+/// updated with new [Options] without losing any of the formatting of a hand-edited file.
+///
+/// Example:
+/// ```
+/// use octopt::RawIni;
+///
+/// let mut ini: RawIni = "# Runs too fast otherwise\ncore.tickrate=20\n".parse().unwrap();
+/// let mut options = ini.options().unwrap();
+/// options.tickrate = Some(30);
+/// ini.set_options(options);
+/// assert!(ini.to_string().starts_with("# Runs too fast otherwise\ncore.tickrate=30\n"));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RawIni {
+    lines: Vec<String>,
+}
+
+impl RawIni {
+    /// Deserializes the Options in this INI file.
+    ///
+    /// # Errors
+    ///
+    /// Returns an `Err` if deserialization from the INI failed.
+    pub fn options(&self) -> Result<Options, serde_ini::de::Error> {
+        Options::from_ini(&self.to_string())
+    }
+
+    /// Replaces the values in this INI file with the given Options. Keys that are already in the
+    /// file keep their position, keys that aren't are added at the end, and keys for values that
+    /// aren't set in `options` are removed. Comments and blank lines are left alone.
+    pub fn set_options(&mut self, options: Options) {
+        let mut values: Vec<(String, String)> = OptionsIni::from(options)
+            .to_string()
+            .lines()
+            .filter_map(|line| line.split_once('='))
+            .map(|(key, value)| (key.to_string(), value.to_string()))
+            .collect();
+        let newline = if self.lines.iter().any(|line| line.ends_with("\r\n")) {
+            "\r\n"
+        } else {
+            "\n"
+        };
+
+        self.lines.retain_mut(|line| {
+            let Some((key, old_value)) = split_key_value(line) else {
+                return true;
+            };
+            let Some(index) = values.iter().position(|(k, _)| k == key.trim()) else {
+                return false;
+            };
+            let (_, value) = values.remove(index);
+            let spacing = &old_value[..old_value.len() - old_value.trim_start().len()];
+            let terminator = &line[line.trim_end_matches(['\r', '\n']).len()..];
+            *line = format!("{}={}{}{}", key, spacing, value, terminator);
+            true
+        });
+
+        if let Some(last) = self.lines.last_mut() {
+            if !values.is_empty() && !last.ends_with('\n') {
+                last.push_str(newline);
+            }
+        }
+        for (key, value) in values {
+            self.lines.push(format!("{}={}{}", key, value, newline));
+        }
+    }
+}
+
+// Splits a line into a key and a value, if it's a key/value pair rather than a comment, a section
+// header or a blank line.
+fn split_key_value(line: &str) -> Option<(&str, &str)> {
+    let trimmed = line.trim();
+    if trimmed.starts_with(['#', ';', '[']) {
+        return None;
+    }
+    line.trim_end_matches(['\r', '\n']).split_once('=')
+}
+
+/// Parses an INI file, keeping its comments and formatting.
+impl FromStr for RawIni {
+    type Err = serde_ini::de::Error;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        OptionsIni::from_str(s)?;
+        Ok(Self {
+            lines: s.split_inclusive('\n').map(String::from).collect(),
+        })
+    }
+}
+
+/// Serializes the INI file, with its comments and formatting intact.
+impl fmt::Display for RawIni {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for line in &self.lines {
+            write!(f, "{}", line)?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Default, PartialEq, Serialize, Deserialize)]
 pub(crate) enum FontIni {
+    #[default]
     #[serde(rename = "octo")]
     Octo,
     #[serde(rename = "vip")]
@@ -400,6 +538,8 @@ pub(crate) enum FontIni {
     Fish,
     #[serde(rename = "akouz1")]
     AKouZ1,
+    #[serde(rename = "none")]
+    None,
 }
 
 impl From<Font> for FontIni {
@@ -412,6 +552,7 @@ impl From<Font> for FontIni {
             Font::Schip => Self::Schip,
             Font::Fish => Self::Fish,
             Font::AKouZ1 => Self::AKouZ1,
+            Font::None => Self::None,
         }
     }
 }
@@ -426,31 +567,36 @@ impl From<FontIni> for Font {
             FontIni::Schip => Self::Schip,
             FontIni::Fish => Self::Fish,
             FontIni::AKouZ1 => Self::AKouZ1,
+            FontIni::None => Self::None,
         }
     }
 }
 
-fn int_from_some_bool<S>(some_bool: &Option<bool>, serializer: S) -> Result<S::Ok, S::Error>
+// Quirks are written as 0 or 1, or as their names for modes that aren't a boolean in Octo, like
+// `clip_x_wrap_y`.
+fn int_from_some_mode<M, S>(some_mode: &Option<M>, serializer: S) -> Result<S::Ok, S::Error>
 where
+    M: BoolOrMode,
     S: Serializer,
 {
     // This function will only be called during serialization, and we know that the argument isn't
     // None because we skip serializing these fields if they're None, so we simply unwrap here. If this
     // panics, some serde derive is missing, or we're using this function for something it's not
     // intended.
-    serializer.serialize_u8(if some_bool.unwrap() { 1 } else { 0 })
+    let mode = some_mode.unwrap();
+    match mode.to_bool() {
+        Some(value) => serializer.serialize_u8(u8::from(value)),
+        None => serializer.serialize_str(&mode.to_string()),
+    }
 }
 
-fn some_bool_from_int<'de, D>(deserializer: D) -> Result<Option<bool>, D::Error>
+fn some_mode_from_int<'de, D, M>(deserializer: D) -> Result<Option<M>, D::Error>
 where
     D: Deserializer<'de>,
+    M: BoolOrMode,
 {
-    match (String::deserialize(deserializer)?).parse::<u8>().unwrap() {
-        1 => Ok(Some(true)),
-        0 => Ok(Some(false)),
-        other => Err(de::Error::invalid_value(
-            Unexpected::Unsigned(u64::from(other)),
-            &"zero or one",
-        )),
-    }
+    // INI values are always strings
+    BoolOrU8OrStr::Str(String::deserialize(deserializer)?)
+        .into_mode()
+        .map(Some)
 }
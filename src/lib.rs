@@ -7,24 +7,42 @@
 //! Games often require specific behavior from its interpreter to run correctly, but you can't know what behavior it expects just by looking at its bytecode.
 //!
 //! This library contains structs and enums that represent all possible CHIP-8 options, which you can use for your CHIP-8 emulator.
+//!
+//! # Features
+//!
+//! * `clap`: Implements `clap::ValueEnum` for the enums, so they can be used as command-line
+//!   arguments.
 
 pub mod color;
 use color::Color;
+use css_color_parser2::ColorParseError;
 mod ini;
 use ini::OptionsIni;
+pub use ini::RawIni;
+mod octocart;
+mod raw_options;
+pub use raw_options::RawOptions;
+mod schema;
+mod short_quirks;
+#[cfg(feature = "clap")]
+mod value_enum;
 use parse_display::{Display, FromStr};
 use serde::de::{self, Deserializer, Unexpected};
 use serde::{Deserialize, Serialize};
 use serde_repr::{Deserialize_repr, Serialize_repr};
-use serde_with::skip_serializing_none;
+use serde_with::base64::Base64;
+use serde_with::{serde_as, skip_serializing_none};
+use short_quirks::ShortQuirks;
+use std::collections::{BTreeMap, HashMap};
 use std::fmt;
+use std::io::{self, Read, Write};
+use std::ops::RangeInclusive;
 use std::str::FromStr;
-use std::u8;
 
 /// If the CHIP-8 interpreter supports custom colors for visual elements, it can use these values
 /// for setting them.
 #[skip_serializing_none]
-#[derive(Debug, PartialEq, Serialize, Deserialize)]
+#[derive(Debug, PartialEq, Serialize, Deserialize, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct Colors {
     /// The standard color used for active pixels on the CHIP-8 screen. For XO-CHIP, it's used for
@@ -37,9 +55,23 @@ pub struct Colors {
     /// The standard background color of the CHIP-8 screen.
     pub background_color: Option<Color>,
     /// The color used by any visual indicator for when the sound buzzer is active.
+    ///
+    /// Some SUPER-CHIP era tools call this `buzzer` or `buzzerColor`, which is accepted when
+    /// deserializing.
+    #[serde(alias = "buzzer", alias = "buzzerColor")]
     pub buzz_color: Option<Color>,
     /// The color used by any visual indicator for when the sound buzzer is inactive.
+    ///
+    /// Some SUPER-CHIP era tools call this `silence` or `silenceColor`, which is accepted when
+    /// deserializing.
+    #[serde(alias = "silence", alias = "silenceColor")]
     pub quiet_color: Option<Color>,
+    /// XO-CHIP only: Whether pixels that are off in every drawing plane are transparent, so the
+    /// game can draw over other content, like a host application's. If so, the interpreter
+    /// should skip clearing the screen to `background_color`. `None` means not transparent.
+    ///
+    /// Not supported by the INI format.
+    pub background_transparent: Option<bool>,
 }
 
 /// The default colorscheme here is white on black, which is most common, with non-standard colors
@@ -61,6 +93,360 @@ impl Default for Colors {
             background_color: Some(Color { r: 0, g: 0, b: 0 }),
             buzz_color: Some(Color { r: 153, g: 0, b: 0 }),
             quiet_color: Some(Color { r: 51, g: 0, b: 0 }),
+            background_transparent: None,
+        }
+    }
+}
+
+impl Colors {
+    /// Replaces every color with the closest color in `palette`, as determined by
+    /// [`Color::nearest_in`]. Colors that are `None` are left alone.
+    pub fn quantize(&mut self, palette: &[Color]) {
+        for color in [
+            &mut self.fill_color,
+            &mut self.fill_color2,
+            &mut self.blend_color,
+            &mut self.background_color,
+            &mut self.buzz_color,
+            &mut self.quiet_color,
+        ]
+        .into_iter()
+        .flatten()
+        {
+            *color = color.nearest_in(palette);
+        }
+    }
+
+    /// Returns the colors of the four combinations of XO-CHIP's two drawing planes, indexed by
+    /// plane number like XO-CHIP does: 0 is `background_color` (no planes), 1 is `fill_color`
+    /// (the first plane), 2 is `fill_color2` (the second plane) and 3 is `blend_color` (both
+    /// planes). `buzz_color` and `quiet_color` aren't included.
+    pub fn as_planes(&self) -> [Option<Color>; 4] {
+        [
+            self.background_color,
+            self.fill_color,
+            self.fill_color2,
+            self.blend_color,
+        ]
+    }
+
+    /// Creates Colors from the colors of the four combinations of XO-CHIP's two drawing planes,
+    /// indexed like in [`Colors::as_planes`], and the colors of the sound buzzer.
+    pub fn from_planes(
+        planes: [Option<Color>; 4],
+        buzz_color: Option<Color>,
+        quiet_color: Option<Color>,
+    ) -> Colors {
+        let [background_color, fill_color, fill_color2, blend_color] = planes;
+        Colors {
+            fill_color,
+            fill_color2,
+            blend_color,
+            background_color,
+            buzz_color,
+            quiet_color,
+            background_transparent: None,
+        }
+    }
+
+    /// Parses a palette string, ie. six colors separated by commas or pipes, in plane order like
+    /// [`Colors::as_planes`] followed by the buzzer colors: background, fill, fill 2, blend, buzz
+    /// and quiet. The colors can be in any format [Color] can parse.
+    ///
+    /// Example:
+    /// ```
+    /// use octopt::Colors;
+    ///
+    /// let colors = Colors::from_palette_string("#000000|#FFFFFF|#FFFF00|#FF0000|#990000|#330000");
+    /// assert_eq!(colors.unwrap(), Colors::default());
+    /// ```
+    pub fn from_palette_string(palette: &str) -> Result<Colors, ColorParseError> {
+        let colors = palette
+            .split([',', '|'])
+            .map(Color::from_str)
+            .collect::<Result<Vec<_>, _>>()?;
+        match colors[..] {
+            [background, fill, fill2, blend, buzz, quiet] => Ok(Colors::from_planes(
+                [Some(background), Some(fill), Some(fill2), Some(blend)],
+                Some(buzz),
+                Some(quiet),
+            )),
+            _ => Err(ColorParseError),
+        }
+    }
+
+    /// Serializes just these colors to a JSON object, with the same keys as in the JSON of
+    /// [Options], like `fillColor`. Colors that are `None` are left out.
+    ///
+    /// Example:
+    /// ```
+    /// use octopt::Colors;
+    /// use octopt::color::Color;
+    ///
+    /// let colors = Colors::from_planes([None, Some(Color::new(255, 204, 0)), None, None], None, None);
+    /// assert_eq!(colors.to_json(), r##"{"fillColor":"#FFCC00"}"##);
+    /// ```
+    pub fn to_json(&self) -> String {
+        serde_json::to_string(self).unwrap_or_default()
+    }
+
+    /// Returns these colors as a comma separated palette string that can be parsed by
+    /// [`Colors::from_palette_string`]. Colors that are `None` are replaced with the
+    /// [default](Colors::default) colors.
+    pub fn to_palette_string(&self) -> String {
+        let default = Colors::default();
+        let planes = self.as_planes().into_iter().zip(default.as_planes());
+        let buzzer = [
+            (self.buzz_color, default.buzz_color),
+            (self.quiet_color, default.quiet_color),
+        ];
+        planes
+            .chain(buzzer)
+            .filter_map(|(color, default)| color.or(default))
+            .map(|color| color.to_string())
+            .collect::<Vec<_>>()
+            .join(",")
+    }
+
+    /// Returns a copy of these colors where each color is converted to the shade of gray with the
+    /// same luminance, as determined by [`Color::to_grayscale`]. Useful for previewing how a game
+    /// will look on a monochrome display.
+    pub fn to_grayscale(&self) -> Colors {
+        self.map(Color::to_grayscale)
+    }
+
+    /// Guesses whether `fill_color` and `background_color` have been swapped, which some very old
+    /// exports did. This is the case if both are set to something other than the
+    /// [default](Colors::default) colors, and the background is brighter than the fill color, as
+    /// determined by [`Color::to_grayscale`]. Note that this is just a heuristic: some games
+    /// really do draw dark pixels on a bright background.
+    pub fn looks_swapped(&self) -> bool {
+        let default = Colors::default();
+        match (self.fill_color, self.background_color) {
+            (Some(fill), Some(background))
+                if self.fill_color != default.fill_color
+                    && self.background_color != default.background_color =>
+            {
+                background.to_grayscale().r > fill.to_grayscale().r
+            }
+            _ => false,
+        }
+    }
+
+    /// Swaps `fill_color` and `background_color`, to repair colors that
+    /// [look swapped](Colors::looks_swapped).
+    pub fn unswap(&mut self) {
+        std::mem::swap(&mut self.fill_color, &mut self.background_color);
+    }
+
+    /// Replaces every color with its complement, where each channel is subtracted from 255, for
+    /// previewing a game with inverted colors. Colors that are `None` are left alone.
+    pub fn invert(&mut self) {
+        *self = self.map(|color| Color::new(255 - color.r, 255 - color.g, 255 - color.b));
+    }
+
+    /// Returns the mean of the colors that are set, channel by channel and rounded to the nearest
+    /// value, for things like a themed background that matches the palette's overall mood. Colors
+    /// that are `None` are ignored, and if none are set, the result is black.
+    ///
+    /// Example:
+    /// ```
+    /// use octopt::Colors;
+    /// use octopt::color::Color;
+    ///
+    /// let colors = Colors {
+    ///     fill_color: Some(Color::new(255, 255, 255)),
+    ///     background_color: Some(Color::new(0, 0, 0)),
+    ///     fill_color2: None,
+    ///     blend_color: None,
+    ///     buzz_color: None,
+    ///     quiet_color: None,
+    ///     background_transparent: None,
+    /// };
+    /// assert_eq!(colors.average(), Color::new(128, 128, 128));
+    /// ```
+    pub fn average(&self) -> Color {
+        let colors: Vec<Color> = self
+            .clone()
+            .into_iter()
+            .filter_map(|(_, color)| color)
+            .collect();
+        if colors.is_empty() {
+            return Color::default();
+        }
+        let mean = |channel: fn(&Color) -> u8| {
+            let sum: usize = colors.iter().map(|color| usize::from(channel(color))).sum();
+            ((sum + colors.len() / 2) / colors.len()) as u8
+        };
+        Color::new(mean(|c| c.r), mean(|c| c.g), mean(|c| c.b))
+    }
+
+    /// Checks whether these colors are equal to `other` when colors that are `None` are treated
+    /// as the [default](Colors::default) colors, ie. whether they render identically.
+    pub fn semantically_eq(&self, other: &Colors) -> bool {
+        let mut bytes = Vec::new();
+        let mut other_bytes = Vec::new();
+        self.write_canonical(&mut bytes);
+        other.write_canonical(&mut other_bytes);
+        bytes == other_bytes
+    }
+
+    /// Returns the roles of the colors that differ from the [default](Colors::default) colors, in
+    /// the order the fields are declared. Colors that are `None` aren't customized, since they
+    /// render as the default colors.
+    ///
+    /// Example:
+    /// ```
+    /// use octopt::{ColorRole, Colors};
+    /// use octopt::color::Color;
+    ///
+    /// let colors = Colors { fill_color: Some(Color::new(255, 204, 0)), ..Colors::default() };
+    /// assert_eq!(colors.customized_fields(), vec![ColorRole::Fill]);
+    /// ```
+    pub fn customized_fields(&self) -> Vec<ColorRole> {
+        self.clone()
+            .into_iter()
+            .zip(Colors::default())
+            .filter(|((_, color), (_, default))| color.is_some() && color != default)
+            .map(|((role, _), _)| role)
+            .collect()
+    }
+
+    // Writes a canonical byte representation of these colors, where `None` is resolved to the
+    // default color, for fingerprinting and comparison.
+    fn write_canonical(&self, bytes: &mut Vec<u8>) {
+        let default = Colors::default();
+        for (color, default) in [
+            (self.fill_color, default.fill_color),
+            (self.fill_color2, default.fill_color2),
+            (self.blend_color, default.blend_color),
+            (self.background_color, default.background_color),
+            (self.buzz_color, default.buzz_color),
+            (self.quiet_color, default.quiet_color),
+        ] {
+            let color = color.or(default).unwrap_or_default();
+            bytes.extend([color.r, color.g, color.b]);
+        }
+        bytes.push(u8::from(self.background_transparent.unwrap_or_default()));
+    }
+
+    fn map(&self, f: impl Fn(&Color) -> Color) -> Colors {
+        Colors {
+            fill_color: self.fill_color.as_ref().map(&f),
+            fill_color2: self.fill_color2.as_ref().map(&f),
+            blend_color: self.blend_color.as_ref().map(&f),
+            background_color: self.background_color.as_ref().map(&f),
+            buzz_color: self.buzz_color.as_ref().map(&f),
+            quiet_color: self.quiet_color.as_ref().map(&f),
+            background_transparent: self.background_transparent,
+        }
+    }
+}
+
+/// Iterates over the six colors along with their roles, in the order the fields are declared:
+/// fill, fill 2, blend, background, buzz and quiet.
+///
+/// Example:
+/// ```
+/// use octopt::{ColorRole, Colors};
+///
+/// let roles: Vec<ColorRole> = Colors::default().into_iter().map(|(role, _)| role).collect();
+/// assert_eq!(roles[0], ColorRole::Fill);
+/// assert_eq!(roles.len(), 6);
+/// ```
+impl IntoIterator for Colors {
+    type Item = (ColorRole, Option<Color>);
+    type IntoIter = std::array::IntoIter<Self::Item, 6>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        [
+            (ColorRole::Fill, self.fill_color),
+            (ColorRole::Fill2, self.fill_color2),
+            (ColorRole::Blend, self.blend_color),
+            (ColorRole::Background, self.background_color),
+            (ColorRole::Buzz, self.buzz_color),
+            (ColorRole::Quiet, self.quiet_color),
+        ]
+        .into_iter()
+    }
+}
+
+/// The role of each of the colors in [Colors].
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
+#[non_exhaustive]
+pub enum ColorRole {
+    /// [`Colors::fill_color`]
+    Fill,
+    /// [`Colors::fill_color2`]
+    Fill2,
+    /// [`Colors::blend_color`]
+    Blend,
+    /// [`Colors::background_color`]
+    Background,
+    /// [`Colors::buzz_color`]
+    Buzz,
+    /// [`Colors::quiet_color`]
+    Quiet,
+}
+
+/// Versions of Octo that accept different sets of option keys, for exporting options with
+/// [`Options::to_json_for_octo_version`].
+#[derive(Display, FromStr, Debug, Default, PartialEq, Eq, Copy, Clone)]
+#[display(style = "snake_case")]
+#[non_exhaustive]
+pub enum OctoVersion {
+    /// Older versions of Octo, which only know the tickrate, the colors, the screen rotation and
+    /// the original quirks: `shiftQuirks`, `loadStoreQuirks`, `vfOrderQuirks`, `clipQuirks`,
+    /// `jumpQuirks` and `vBlankQuirks`.
+    Classic,
+    /// The current version of Octo, which also knows `maxSize`, `touchInputMode`, `fontStyle` and
+    /// `logicQuirks`.
+    #[default]
+    Current,
+}
+
+impl OctoVersion {
+    // The JSON keys this version of Octo accepts
+    fn keys(&self) -> &'static [&'static str] {
+        const CLASSIC: [&str; 14] = [
+            "tickrate",
+            "fillColor",
+            "fillColor2",
+            "blendColor",
+            "backgroundColor",
+            "buzzColor",
+            "quietColor",
+            "screenRotation",
+            "shiftQuirks",
+            "loadStoreQuirks",
+            "vfOrderQuirks",
+            "clipQuirks",
+            "jumpQuirks",
+            "vBlankQuirks",
+        ];
+        const CURRENT: [&str; 18] = [
+            "tickrate",
+            "fillColor",
+            "fillColor2",
+            "blendColor",
+            "backgroundColor",
+            "buzzColor",
+            "quietColor",
+            "screenRotation",
+            "shiftQuirks",
+            "loadStoreQuirks",
+            "vfOrderQuirks",
+            "clipQuirks",
+            "jumpQuirks",
+            "vBlankQuirks",
+            "maxSize",
+            "touchInputMode",
+            "fontStyle",
+            "logicQuirks",
+        ];
+        match self {
+            OctoVersion::Classic => &CLASSIC,
+            OctoVersion::Current => &CURRENT,
         }
     }
 }
@@ -92,12 +478,13 @@ pub enum Platform {
 }
 
 /// Represents the different touch modes supported by [Octo](https://github.com/JohnEarnest/Octo).
-#[derive(Display, FromStr, Debug, PartialEq, Serialize, Deserialize)]
+#[derive(Display, FromStr, Debug, Default, PartialEq, Serialize, Deserialize, Copy, Clone)]
 #[serde(rename_all = "lowercase")]
 #[display(style = "lowercase")]
 #[non_exhaustive]
 pub enum TouchMode {
     /// Do not attempt to handle touch input.
+    #[default]
     None,
     /// Taps on the screen are treated like pressing key 6. Swipes or dragging and holding on the
     /// screen are treated like a virtual directional pad based on keys 5,8,7 and 9.
@@ -115,12 +502,6 @@ pub enum TouchMode {
     Vip,
 }
 
-impl Default for TouchMode {
-    fn default() -> Self {
-        Self::None
-    }
-}
-
 /// Represents the different "quirks", ie. divergent behaviors, of the CHIP-8 runtime. These are
 /// the most important ones to support, as many games depend on specific settings here to run
 /// properly.
@@ -146,8 +527,14 @@ impl Default for TouchMode {
 /// Note also that Octo doesn't support all of these quirks. This struct should support all
 /// possible divergent behaviors between widely used CHIP-8 interpreters. A CHIP-8 interpreter
 /// should ignore any quirks they don't recognize, or don't have any intention of supporting.
+///
+/// When deserializing, the quirk keys are also accepted in all lowercase, like `vblankquirks`,
+/// since some tools lowercase them. Newer tools nest the quirks in a `quirks` object instead, with
+/// short names like `shift` instead of `shiftQuirks`, which is accepted too. They're always
+/// serialized with Octo's names; see [`Quirks::to_json_short_names`] and
+/// [`Options::to_json_short_quirk_names`] for writing the short names.
 #[skip_serializing_none]
-#[derive(Debug, PartialEq, Serialize, Deserialize)]
+#[derive(Debug, PartialEq, Serialize, Deserialize, Clone)]
 #[serde(rename_all = "camelCase")]
 #[non_exhaustive]
 pub struct Quirks {
@@ -158,21 +545,23 @@ pub struct Quirks {
     /// SUPER-CHIP behavior)
     #[serde(
         rename = "shiftQuirks",
+        alias = "shiftquirks",
         deserialize_with = "some_bool_from_int",
         default
     )]
     pub shift: Option<bool>,
     /// Decides the behavior of the CHIP-8 serialization FX55 (dump registers V0–VX to memory
     /// location I) and FX65 (load registers V0–VX from memory location I):
-    /// * False: The value in the I register is incremented for each register loaded/stored.
-    /// (Original behavior)
-    /// * True: The I register is left unchanged after the operation. (SUPER-CHIP behavior)
-    #[serde(
-        rename = "loadStoreQuirks",
-        deserialize_with = "some_bool_from_int",
-        default
-    )]
-    pub load_store: Option<bool>,
+    /// * IncrementByXPlus1: The value in the I register is incremented for each register
+    /// loaded/stored, ie. by X+1 (original behavior)
+    /// * IncrementByX: The I register is incremented by X, one less than the number of registers
+    /// (some CHIP-48 era interpreters)
+    /// * Unchanged: The I register is left unchanged after the operation (SUPER-CHIP behavior)
+    ///
+    /// For compatibility with Octo, `false`/`0` and `true`/`1` are read as IncrementByXPlus1 and
+    /// Unchanged, and those two are written as booleans.
+    #[serde(rename = "loadStoreQuirks", alias = "loadstorequirks", default)]
+    pub load_store: Option<LoadStoreMode>,
     /// Decides the behavior of the CHIP-8 relative jump instruction BXNN (jump to address XNN,
     /// plus the value in a register):
     /// * False: The value in the V0 register is used for the offset (original behavior)
@@ -180,6 +569,7 @@ pub struct Quirks {
     /// address XNN (CHIP48 and SUPER-CHIP behavior)
     #[serde(
         rename = "jumpQuirks",
+        alias = "jumpquirks",
         deserialize_with = "some_bool_from_int",
         default
     )]
@@ -192,19 +582,20 @@ pub struct Quirks {
     /// behavior)
     #[serde(
         rename = "logicQuirks",
+        alias = "logicquirks",
         deserialize_with = "some_bool_from_int",
         default
     )]
     pub logic: Option<bool>,
     /// Decides the behavior of sprites drawn out of bounds:
-    /// * False: Sprites wrap on screen edges (Octo behavior)
-    /// * True: Sprites are clipped on screen edges (original, CHIP-48 and SUPER-CHIP behavior)
-    #[serde(
-        rename = "clipQuirks",
-        deserialize_with = "some_bool_from_int",
-        default
-    )]
-    pub clip: Option<bool>,
+    /// * WrapBoth: Sprites wrap on screen edges (Octo behavior)
+    /// * ClipBoth: Sprites are clipped on screen edges (original, CHIP-48 and SUPER-CHIP behavior)
+    /// * ClipXWrapY and WrapXClipY: Sprites are clipped on one axis and wrap on the other
+    ///
+    /// For compatibility with Octo, `false`/`0` and `true`/`1` are read as WrapBoth and ClipBoth,
+    /// and those two are written as booleans.
+    #[serde(rename = "clipQuirks", alias = "clipquirks", default)]
+    pub clip: Option<ClipMode>,
     /// Decides whether the CHIP-8 interpreter should wait for the rest of the current frame after
     /// each drawing operation:
     /// * False: No special behavior (CHIP-48, SUPER-CHIP and Octo behavior)
@@ -212,6 +603,8 @@ pub struct Quirks {
     /// it waits for a "VBlank interrupt" (original behavior)
     #[serde(
         rename = "vBlankQuirks",
+        alias = "vblankQuirks",
+        alias = "vblankquirks",
         deserialize_with = "some_bool_from_int",
         default
     )]
@@ -223,6 +616,7 @@ pub struct Quirks {
     /// (original behavior)
     #[serde(
         rename = "vfOrderQuirks",
+        alias = "vforderquirks",
         deserialize_with = "some_bool_from_int",
         default
     )]
@@ -233,7 +627,11 @@ pub struct Quirks {
     /// * TallSprite: Draw a 16-byte sprite (DREAM 6800 behavior)
     /// * BigSprite: Draw a 16x16 pixel sprite, ie. the same behavior as in hires (high-resolution
     /// 128x64 SUPER-CHIP/XO-CHIP) mode (Octo behavior)
-    #[serde(rename = "loresDXY0Quirks")]
+    #[serde(
+        rename = "loresDXY0Quirks",
+        alias = "loresDxy0Quirks",
+        alias = "loresdxy0quirks"
+    )]
     pub lores_dxy0: Option<LoResDxy0Behavior>,
     /// Decides whether the screen should be cleared when there is a resolution change (00FE and
     /// 00FF). Note that if this is true, then the screen should retain the current image when
@@ -244,6 +642,7 @@ pub struct Quirks {
     /// behavior)
     #[serde(
         rename = "resClearQuirks",
+        alias = "resclearquirks",
         deserialize_with = "some_bool_from_int",
         default
     )]
@@ -254,6 +653,7 @@ pub struct Quirks {
     /// * False: The delay timer counts down to 0, and then stops (original behavior)
     #[serde(
         rename = "delayWrapQuirks",
+        alias = "delaywrapquirks",
         deserialize_with = "some_bool_from_int",
         default
     )]
@@ -265,6 +665,7 @@ pub struct Quirks {
     /// * False: VF is always set to 1 if there is a collision (original behavior)
     #[serde(
         rename = "hiresCollisionQuirks",
+        alias = "hirescollisionquirks",
         deserialize_with = "some_bool_from_int",
         default
     )]
@@ -276,6 +677,7 @@ pub struct Quirks {
     /// * False: VF is unchanged if a sprite runs off the bottom of the screen (original behavior)
     #[serde(
         rename = "clipCollisionQuirks",
+        alias = "clipcollisionquirks",
         deserialize_with = "some_bool_from_int",
         default
     )]
@@ -289,6 +691,7 @@ pub struct Quirks {
     /// * False: Scrolling acts the same in high and low resolution mode (Octo behavior)
     #[serde(
         rename = "scrollQuirks",
+        alias = "scrollquirks",
         deserialize_with = "some_bool_from_int",
         default
     )]
@@ -302,21 +705,613 @@ pub struct Quirks {
     /// * False: VF is not affected by the I register (original behavior)
     #[serde(
         rename = "overflowIQuirks",
+        alias = "overflowiquirks",
         deserialize_with = "some_bool_from_int",
         default
     )]
     pub overflow_i: Option<bool>,
 }
 
+impl Quirks {
+    /// Serializes these Quirks to JSON with the short names used by newer tools, like `shift`
+    /// instead of Octo's `shiftQuirks`, as they're written in a nested `quirks` object.
+    ///
+    /// Example:
+    /// ```
+    /// use octopt::Quirks;
+    ///
+    /// let mut quirks = Quirks::default();
+    /// quirks.shift = Some(true);
+    /// assert!(quirks.to_json_short_names().contains("\"shift\":true"));
+    /// ```
+    pub fn to_json_short_names(&self) -> String {
+        serde_json::to_string(&ShortQuirks::from(self.clone())).unwrap_or_default()
+    }
+
+    /// Returns a short, human-readable description of the quirk with the given JSON key (for
+    /// example `shiftQuirks`), suitable for tooltips in a settings UI. Returns `None` if the key
+    /// isn't a known quirk.
+    pub fn describe(key: &str) -> Option<&'static str> {
+        Some(match key {
+            "shiftQuirks" => "Shift instructions (8XY6 and 8XYE) shift VX in place and ignore VY, instead of shifting VY into VX.",
+            "loadStoreQuirks" => "Load and store instructions (FX55 and FX65) leave I unchanged or increment it by X, instead of incrementing it for each register.",
+            "jumpQuirks" => "The jump instruction BXNN adds VX to the address, where X is the first digit of XNN, instead of V0.",
+            "logicQuirks" => "Logical instructions (8XY1, 8XY2 and 8XY3) leave VF in an undefined state, instead of unchanged.",
+            "clipQuirks" => "Sprites drawn at the edges of the screen are clipped, instead of wrapping around, either on both axes or only one.",
+            "vBlankQuirks" => "Drawing a sprite waits for the rest of the frame, like the vertical blank interrupt on the COSMAC VIP.",
+            "vfOrderQuirks" => "Arithmetic instructions with VF as an operand put the flag in VF, instead of the result.",
+            "loresDXY0Quirks" => "What DXY0 (drawing a sprite with height 0) does in low resolution mode.",
+            "resClearQuirks" => "Changing the screen resolution clears the screen, instead of keeping the image.",
+            "delayWrapQuirks" => "The delay timer wraps around from 0 to 255 and keeps counting, instead of stopping at 0.",
+            "hiresCollisionQuirks" => "Sprite collisions in high resolution mode set VF to the number of colliding rows, instead of 1.",
+            "clipCollisionQuirks" => "Sprites clipped at the bottom of the screen count as a collision.",
+            "scrollQuirks" => "Scrolling in low resolution mode scrolls by half as many pixels as in high resolution mode.",
+            "overflowIQuirks" => "VF is set to 1 if the I register overflows past 0x0FFF.",
+            _ => return None,
+        })
+    }
+
+    /// Returns a copy of these quirks where every quirk that is `Some(true)` is `Some(false)` and
+    /// vice versa. Quirks that are `None` are left alone, as is `lores_dxy0`, which isn't a
+    /// boolean, and `load_store` when it's [`LoadStoreMode::IncrementByX`], which has no opposite.
+    /// Useful for testing both behaviors of an interpreter.
+    pub fn inverted(&self) -> Quirks {
+        let invert = |quirk: Option<bool>| quirk.map(|quirk| !quirk);
+        Quirks {
+            shift: invert(self.shift),
+            load_store: self.load_store.map(|load_store| match load_store {
+                LoadStoreMode::IncrementByXPlus1 => LoadStoreMode::Unchanged,
+                LoadStoreMode::IncrementByX => LoadStoreMode::IncrementByX,
+                LoadStoreMode::Unchanged => LoadStoreMode::IncrementByXPlus1,
+            }),
+            jump0: invert(self.jump0),
+            logic: invert(self.logic),
+            clip: self.clip.map(|clip| match clip {
+                ClipMode::WrapBoth => ClipMode::ClipBoth,
+                ClipMode::ClipBoth => ClipMode::WrapBoth,
+                ClipMode::ClipXWrapY => ClipMode::WrapXClipY,
+                ClipMode::WrapXClipY => ClipMode::ClipXWrapY,
+            }),
+            vblank: invert(self.vblank),
+            vf_order: invert(self.vf_order),
+            lores_dxy0: self.lores_dxy0,
+            res_clear: invert(self.res_clear),
+            delay_wrap: invert(self.delay_wrap),
+            hires_collision: invert(self.hires_collision),
+            clip_collision: invert(self.clip_collision),
+            scroll: invert(self.scroll),
+            overflow_i: invert(self.overflow_i),
+        }
+    }
+
+    /// Decides every quirk, so an interpreter can run with them. Quirks that aren't set get the
+    /// value they have in [`Quirks::default`], which is Octo's behavior.
+    pub fn resolve(&self) -> ResolvedQuirks {
+        let quirks = self.clone().or(Quirks::default());
+        ResolvedQuirks {
+            shift: quirks.shift.unwrap_or_default(),
+            load_store: quirks.load_store.unwrap_or_default(),
+            jump0: quirks.jump0.unwrap_or_default(),
+            logic: quirks.logic.unwrap_or_default(),
+            clip: quirks.clip.unwrap_or_default(),
+            vblank: quirks.vblank.unwrap_or_default(),
+            vf_order: quirks.vf_order.unwrap_or_default(),
+            lores_dxy0: quirks.lores_dxy0.unwrap_or_default(),
+            res_clear: quirks.res_clear.unwrap_or_default(),
+            delay_wrap: quirks.delay_wrap.unwrap_or_default(),
+            hires_collision: quirks.hires_collision.unwrap_or_default(),
+            clip_collision: quirks.clip_collision.unwrap_or_default(),
+            scroll: quirks.scroll.unwrap_or_default(),
+            overflow_i: quirks.overflow_i.unwrap_or_default(),
+        }
+    }
+
+    /// Decides every quirk, like [`Quirks::resolve`], except that quirks that aren't set get the
+    /// value they have on the given `platform`.
+    pub fn resolve_for(&self, platform: Platform) -> ResolvedQuirks {
+        self.clone().or(Quirks::from(platform)).resolve()
+    }
+
+    /// Serializes only the quirks that differ from Octo's defaults (see [`Quirks::default`]),
+    /// like Octo does when it exports options, so the result is an empty object for quirks that
+    /// match Octo's own behavior. Quirks that are `None` are left out as usual.
+    pub fn to_json_octo_minimal(&self) -> serde_json::Value {
+        let defaults = serde_json::to_value(Quirks::default()).unwrap_or_default();
+        let mut json = serde_json::to_value(self).unwrap_or_default();
+        if let Some(quirks) = json.as_object_mut() {
+            quirks.retain(|key, value| defaults.get(key) != Some(value));
+        }
+        json
+    }
+
+    /// Returns a copy of these quirks with only the ones that Octo supports, and every other
+    /// quirk set to `None`, so Options exported for Octo only contain keys it understands.
+    pub fn octo_subset(&self) -> Quirks {
+        Quirks {
+            shift: self.shift,
+            load_store: self.load_store,
+            jump0: self.jump0,
+            logic: self.logic,
+            clip: self.clip,
+            vblank: self.vblank,
+            vf_order: self.vf_order,
+            lores_dxy0: None,
+            res_clear: None,
+            delay_wrap: None,
+            hires_collision: None,
+            clip_collision: None,
+            scroll: None,
+            overflow_i: None,
+        }
+    }
+
+    /// Returns the index of the register that the jump instruction BNNN adds to the address NNN,
+    /// given as `target_addr`. If the `jump0` quirk is enabled, the instruction is BXNN and the
+    /// register is VX, where X is the first digit of NNN; otherwise, it's always V0.
+    pub fn jump_register(&self, target_addr: u16) -> u8 {
+        if self.jump0 == Some(true) {
+            ((target_addr >> 8) & 0xF) as u8
+        } else {
+            0
+        }
+    }
+
+    /// Returns the JSON key and value of every boolean quirk, in the order they're declared.
+    /// `load_store`, `clip` and `lores_dxy0` aren't included, since they aren't booleans; see
+    /// [`Quirks::markdown_table`] for a summary of all quirks.
+    pub fn capability_row(&self) -> Vec<(&'static str, Option<bool>)> {
+        vec![
+            ("shiftQuirks", self.shift),
+            ("jumpQuirks", self.jump0),
+            ("logicQuirks", self.logic),
+            ("vBlankQuirks", self.vblank),
+            ("vfOrderQuirks", self.vf_order),
+            ("resClearQuirks", self.res_clear),
+            ("delayWrapQuirks", self.delay_wrap),
+            ("hiresCollisionQuirks", self.hires_collision),
+            ("clipCollisionQuirks", self.clip_collision),
+            ("scrollQuirks", self.scroll),
+            ("overflowIQuirks", self.overflow_i),
+        ]
+    }
+
+    /// Returns a Markdown table with one row for each quirk, with its JSON key, its value (or
+    /// "unset" if it's `None`) and its [description](Quirks::describe), for documentation.
+    ///
+    /// Example:
+    /// ```
+    /// use octopt::Quirks;
+    ///
+    /// let table = Quirks::default().markdown_table();
+    /// assert!(table.starts_with("| Quirk | Value | Description |\n|---|---|---|\n"));
+    /// assert!(table.contains("| shiftQuirks | false |"));
+    /// ```
+    pub fn markdown_table(&self) -> String {
+        let unset = || "unset".to_string();
+        let mut rows: Vec<(&str, String)> = self
+            .capability_row()
+            .into_iter()
+            .map(|(key, value)| (key, value.map_or_else(unset, |value| value.to_string())))
+            .collect();
+        rows.insert(
+            1,
+            (
+                "loadStoreQuirks",
+                self.load_store
+                    .map_or_else(unset, |load_store| load_store.to_string()),
+            ),
+        );
+        rows.insert(
+            4,
+            (
+                "clipQuirks",
+                self.clip.map_or_else(unset, |clip| clip.to_string()),
+            ),
+        );
+        rows.insert(
+            7,
+            (
+                "loresDXY0Quirks",
+                self.lores_dxy0
+                    .map_or_else(unset, |lores| lores.to_string()),
+            ),
+        );
+
+        let mut table = String::from("| Quirk | Value | Description |\n|---|---|---|\n");
+        for (key, value) in rows {
+            table.push_str(&format!(
+                "| {} | {} | {} |\n",
+                key,
+                value,
+                Quirks::describe(key).unwrap_or_default()
+            ));
+        }
+        table
+    }
+
+    /// Returns a compact summary of these quirks for logging, where each quirk is a letter
+    /// followed by `+` if it's true, `-` if it's false and `?` if it's `None`. The quirks are in
+    /// the order they're declared, with these letters:
+    ///
+    /// | Letter | Quirk | Letter | Quirk |
+    /// |---|---|---|---|
+    /// | `S` | `shift` | `d` | `lores_dxy0` |
+    /// | `l` | `load_store` | `r` | `res_clear` |
+    /// | `j` | `jump0` | `w` | `delay_wrap` |
+    /// | `L` | `logic` | `h` | `hires_collision` |
+    /// | `c` | `clip` | `C` | `clip_collision` |
+    /// | `v` | `vblank` | `s` | `scroll` |
+    /// | `f` | `vf_order` | `o` | `overflow_i` |
+    ///
+    /// For the quirks that aren't booleans, `load_store` is `+` for Unchanged and `-` for
+    /// IncrementByXPlus1, `clip` is `+` for ClipBoth and `-` for WrapBoth, and `lores_dxy0` is `+`
+    /// for BigSprite and `-` for NoOp. Their other modes are `~`.
+    ///
+    /// Example:
+    /// ```
+    /// use octopt::Quirks;
+    ///
+    /// assert_eq!(Quirks::default().summary_flags(), "S-l-j-L-c-v-f-d+r+w-h-C-s-o-");
+    /// ```
+    pub fn summary_flags(&self) -> String {
+        let flag = |quirk: Option<bool>| match quirk {
+            None => '?',
+            Some(true) => '+',
+            Some(false) => '-',
+        };
+        let flags = [
+            ('S', flag(self.shift)),
+            (
+                'l',
+                match self.load_store {
+                    None => '?',
+                    Some(LoadStoreMode::Unchanged) => '+',
+                    Some(LoadStoreMode::IncrementByXPlus1) => '-',
+                    Some(LoadStoreMode::IncrementByX) => '~',
+                },
+            ),
+            ('j', flag(self.jump0)),
+            ('L', flag(self.logic)),
+            (
+                'c',
+                match self.clip {
+                    None => '?',
+                    Some(ClipMode::ClipBoth) => '+',
+                    Some(ClipMode::WrapBoth) => '-',
+                    Some(ClipMode::ClipXWrapY | ClipMode::WrapXClipY) => '~',
+                },
+            ),
+            ('v', flag(self.vblank)),
+            ('f', flag(self.vf_order)),
+            (
+                'd',
+                match self.lores_dxy0 {
+                    None => '?',
+                    Some(LoResDxy0Behavior::BigSprite) => '+',
+                    Some(LoResDxy0Behavior::NoOp) => '-',
+                    Some(LoResDxy0Behavior::TallSprite) => '~',
+                },
+            ),
+            ('r', flag(self.res_clear)),
+            ('w', flag(self.delay_wrap)),
+            ('h', flag(self.hires_collision)),
+            ('C', flag(self.clip_collision)),
+            ('s', flag(self.scroll)),
+            ('o', flag(self.overflow_i)),
+        ];
+        flags
+            .into_iter()
+            .flat_map(|(letter, flag)| [letter, flag])
+            .collect()
+    }
+
+    /// Returns the next value of the delay timer when it's decremented (60 times per second)
+    /// from `current`. If the `delay_wrap` quirk is enabled, the timer wraps around from 0 to
+    /// 255; otherwise, it stops at 0.
+    pub fn delay_timer_next(&self, current: u8) -> u8 {
+        if self.delay_wrap == Some(true) {
+            current.wrapping_sub(1)
+        } else {
+            current.saturating_sub(1)
+        }
+    }
+
+    /// Returns a hash of these quirks, where quirks that are `None` are treated as their
+    /// [defaults](Quirks::default), so games that need the same behavior from the interpreter get
+    /// the same hash. Like [`Options::fingerprint`], it's stable across versions of Rust and
+    /// platforms.
+    pub fn profile_hash(&self) -> u64 {
+        let mut bytes = Vec::new();
+        self.write_canonical(&mut bytes);
+        fnv1a(&bytes)
+    }
+
+    // Writes a canonical byte representation of these quirks, where `None` is resolved to the
+    // default value, for fingerprinting and comparison.
+    fn write_canonical(&self, bytes: &mut Vec<u8>) {
+        let quirks = self.clone().or(Quirks::default());
+        let flag = |quirk: Option<bool>| u8::from(quirk.unwrap_or_default());
+        bytes.extend([
+            flag(quirks.shift),
+            match quirks.load_store.unwrap_or_default() {
+                LoadStoreMode::IncrementByXPlus1 => 0,
+                LoadStoreMode::Unchanged => 1,
+                LoadStoreMode::IncrementByX => 2,
+            },
+            flag(quirks.jump0),
+            flag(quirks.logic),
+            match quirks.clip.unwrap_or_default() {
+                ClipMode::WrapBoth => 0,
+                ClipMode::ClipBoth => 1,
+                ClipMode::ClipXWrapY => 2,
+                ClipMode::WrapXClipY => 3,
+            },
+            flag(quirks.vblank),
+            flag(quirks.vf_order),
+            flag(quirks.res_clear),
+            flag(quirks.delay_wrap),
+            flag(quirks.hires_collision),
+            flag(quirks.clip_collision),
+            flag(quirks.scroll),
+            flag(quirks.overflow_i),
+        ]);
+        bytes.push(match quirks.lores_dxy0.unwrap_or_default() {
+            LoResDxy0Behavior::NoOp => 0,
+            LoResDxy0Behavior::TallSprite => 1,
+            LoResDxy0Behavior::BigSprite => 2,
+        });
+    }
+
+    /// Packs these quirks into an integer, for sharing them compactly, like in a URL or a QR code.
+    /// Each boolean quirk takes two bits, in the order they're declared, where 0 is `None`, 1 is
+    /// `Some(false)` and 2 is `Some(true)`. `load_store` takes two bits in its place among them,
+    /// like a boolean where IncrementByXPlus1 is false and Unchanged is true, and 3 is
+    /// IncrementByX. They're followed by three bits for `clip` and two bits for `lores_dxy0`,
+    /// where 0 is also `None`. The rest of the bits are 0.
+    ///
+    /// Example:
+    /// ```
+    /// use octopt::Quirks;
+    ///
+    /// let quirks = Quirks::default();
+    /// assert_eq!(Quirks::from_bitfield(quirks.to_bitfield()), Some(quirks));
+    /// ```
+    pub fn to_bitfield(&self) -> u32 {
+        let clip = match self.clip {
+            None => 0,
+            Some(ClipMode::WrapBoth) => 1,
+            Some(ClipMode::ClipBoth) => 2,
+            Some(ClipMode::ClipXWrapY) => 3,
+            Some(ClipMode::WrapXClipY) => 4,
+        };
+        let lores_dxy0 = match self.lores_dxy0 {
+            None => 0,
+            Some(LoResDxy0Behavior::NoOp) => 1,
+            Some(LoResDxy0Behavior::TallSprite) => 2,
+            Some(LoResDxy0Behavior::BigSprite) => 3,
+        };
+        let load_store = match self.load_store {
+            None => 0,
+            Some(LoadStoreMode::IncrementByXPlus1) => 1,
+            Some(LoadStoreMode::Unchanged) => 2,
+            Some(LoadStoreMode::IncrementByX) => 3,
+        };
+        let mut flags: Vec<u32> = self
+            .capability_row()
+            .into_iter()
+            .map(|(_, quirk)| match quirk {
+                None => 0,
+                Some(false) => 1,
+                Some(true) => 2,
+            })
+            .collect();
+        flags.insert(1, load_store);
+        flags
+            .into_iter()
+            .enumerate()
+            .fold(clip << 24 | lores_dxy0 << 27, |bits, (index, flag)| {
+                bits | flag << (index * 2)
+            })
+    }
+
+    /// Unpacks quirks that were packed with [`Quirks::to_bitfield`]. Returns `None` if the
+    /// integer isn't a valid packing.
+    pub fn from_bitfield(bits: u32) -> Option<Quirks> {
+        let flag = |index: u32| match bits >> (index * 2) & 0b11 {
+            0 => Some(None),
+            1 => Some(Some(false)),
+            2 => Some(Some(true)),
+            _ => None,
+        };
+        let load_store = match bits >> 2 & 0b11 {
+            0 => None,
+            1 => Some(LoadStoreMode::IncrementByXPlus1),
+            2 => Some(LoadStoreMode::Unchanged),
+            _ => Some(LoadStoreMode::IncrementByX),
+        };
+        let clip = match bits >> 24 & 0b111 {
+            0 => None,
+            1 => Some(ClipMode::WrapBoth),
+            2 => Some(ClipMode::ClipBoth),
+            3 => Some(ClipMode::ClipXWrapY),
+            4 => Some(ClipMode::WrapXClipY),
+            _ => return None,
+        };
+        let lores_dxy0 = match bits >> 27 & 0b11 {
+            0 => None,
+            1 => Some(LoResDxy0Behavior::NoOp),
+            2 => Some(LoResDxy0Behavior::TallSprite),
+            _ => Some(LoResDxy0Behavior::BigSprite),
+        };
+        if bits >> 29 != 0 {
+            return None;
+        }
+        Some(Quirks {
+            shift: flag(0)?,
+            load_store,
+            jump0: flag(2)?,
+            logic: flag(3)?,
+            clip,
+            vblank: flag(4)?,
+            vf_order: flag(5)?,
+            lores_dxy0,
+            res_clear: flag(6)?,
+            delay_wrap: flag(7)?,
+            hires_collision: flag(8)?,
+            clip_collision: flag(9)?,
+            scroll: flag(10)?,
+            overflow_i: flag(11)?,
+        })
+    }
+
+    /// Decides what the logical instructions 8XY1, 8XY2 and 8XY3 do to the VF flag register,
+    /// given its `current_vf` value, so an emulator can behave deterministically. Returns `None`
+    /// if VF should be left unchanged, and otherwise the new value of VF.
+    ///
+    /// When [`Quirks::logic`] is true, VF is undefined after these instructions. On the COSMAC
+    /// VIP it's clobbered by the interpreter, and this returns `Some(0)`, like Octo does. If the
+    /// quirk isn't set, it's treated as false, like in [`Quirks::resolve`].
+    pub fn logic_vf_result(&self, _current_vf: u8) -> Option<u8> {
+        self.logic_or(false).then_some(0)
+    }
+
+    /// Returns notes about how the quirks that are set affect each other, for authors who might
+    /// not know about these relationships. These are informational, and don't mean that the
+    /// quirks are wrong. Currently, this notes that:
+    /// * `scroll` with `res_clear` disabled or unset means the low resolution display is scaled
+    ///   up 2x, so the image should also be scaled up when switching to high resolution
+    /// * `clip_collision` has no effect unless sprites are clipped at the bottom of the screen
+    pub fn interaction_notes(&self) -> Vec<&'static str> {
+        let clips_vertically = matches!(
+            self.clip,
+            Some(ClipMode::ClipBoth) | Some(ClipMode::WrapXClipY)
+        );
+        [
+            (
+                self.scroll == Some(true) && self.res_clear != Some(true),
+                "the scroll quirk assumes the low resolution display is scaled up 2x, so without \
+                 the resClear quirk the image is scaled up 2x when switching to high resolution",
+            ),
+            (
+                self.clip_collision == Some(true) && !clips_vertically,
+                "the clipCollision quirk has no effect unless the clip quirk clips sprites at the \
+                 bottom of the screen",
+            ),
+        ]
+        .into_iter()
+        .filter_map(|(applies, note)| applies.then_some(note))
+        .collect()
+    }
+
+    /// Returns [`Quirks::shift`], or `default` if it's `None`.
+    ///
+    /// Example:
+    /// ```
+    /// use octopt::Quirks;
+    ///
+    /// let mut quirks = Quirks::default();
+    /// quirks.shift = None;
+    /// assert!(quirks.shift_or(true));
+    /// ```
+    pub fn shift_or(&self, default: bool) -> bool {
+        self.shift.unwrap_or(default)
+    }
+
+    /// Returns [`Quirks::load_store`], or `default` if it's `None`.
+    pub fn load_store_or(&self, default: LoadStoreMode) -> LoadStoreMode {
+        self.load_store.unwrap_or(default)
+    }
+
+    /// Returns [`Quirks::jump0`], or `default` if it's `None`.
+    pub fn jump0_or(&self, default: bool) -> bool {
+        self.jump0.unwrap_or(default)
+    }
+
+    /// Returns [`Quirks::logic`], or `default` if it's `None`.
+    pub fn logic_or(&self, default: bool) -> bool {
+        self.logic.unwrap_or(default)
+    }
+
+    /// Returns [`Quirks::clip`], or `default` if it's `None`.
+    pub fn clip_or(&self, default: ClipMode) -> ClipMode {
+        self.clip.unwrap_or(default)
+    }
+
+    /// Returns [`Quirks::vblank`], or `default` if it's `None`.
+    pub fn vblank_or(&self, default: bool) -> bool {
+        self.vblank.unwrap_or(default)
+    }
+
+    /// Returns [`Quirks::vf_order`], or `default` if it's `None`.
+    pub fn vf_order_or(&self, default: bool) -> bool {
+        self.vf_order.unwrap_or(default)
+    }
+
+    /// Returns [`Quirks::lores_dxy0`], or `default` if it's `None`.
+    pub fn lores_dxy0_or(&self, default: LoResDxy0Behavior) -> LoResDxy0Behavior {
+        self.lores_dxy0.unwrap_or(default)
+    }
+
+    /// Returns [`Quirks::res_clear`], or `default` if it's `None`.
+    pub fn res_clear_or(&self, default: bool) -> bool {
+        self.res_clear.unwrap_or(default)
+    }
+
+    /// Returns [`Quirks::delay_wrap`], or `default` if it's `None`.
+    pub fn delay_wrap_or(&self, default: bool) -> bool {
+        self.delay_wrap.unwrap_or(default)
+    }
+
+    /// Returns [`Quirks::hires_collision`], or `default` if it's `None`.
+    pub fn hires_collision_or(&self, default: bool) -> bool {
+        self.hires_collision.unwrap_or(default)
+    }
+
+    /// Returns [`Quirks::clip_collision`], or `default` if it's `None`.
+    pub fn clip_collision_or(&self, default: bool) -> bool {
+        self.clip_collision.unwrap_or(default)
+    }
+
+    /// Returns [`Quirks::scroll`], or `default` if it's `None`.
+    pub fn scroll_or(&self, default: bool) -> bool {
+        self.scroll.unwrap_or(default)
+    }
+
+    /// Returns [`Quirks::overflow_i`], or `default` if it's `None`.
+    pub fn overflow_i_or(&self, default: bool) -> bool {
+        self.overflow_i.unwrap_or(default)
+    }
+
+    // Returns these quirks, with any `None` values replaced by the values in `other`.
+    fn or(self, other: Quirks) -> Quirks {
+        Quirks {
+            shift: self.shift.or(other.shift),
+            load_store: self.load_store.or(other.load_store),
+            jump0: self.jump0.or(other.jump0),
+            logic: self.logic.or(other.logic),
+            clip: self.clip.or(other.clip),
+            vblank: self.vblank.or(other.vblank),
+            vf_order: self.vf_order.or(other.vf_order),
+            lores_dxy0: self.lores_dxy0.or(other.lores_dxy0),
+            res_clear: self.res_clear.or(other.res_clear),
+            delay_wrap: self.delay_wrap.or(other.delay_wrap),
+            hires_collision: self.hires_collision.or(other.hires_collision),
+            clip_collision: self.clip_collision.or(other.clip_collision),
+            scroll: self.scroll.or(other.scroll),
+            overflow_i: self.overflow_i.or(other.overflow_i),
+        }
+    }
+}
+
 /// Returns a default where no quirks are enabled, except the ones Octo observe.
 impl Default for Quirks {
     fn default() -> Self {
         Self {
             shift: Some(false),
-            load_store: Some(false),
+            load_store: Some(LoadStoreMode::IncrementByXPlus1),
             jump0: Some(false),
             logic: Some(false),
-            clip: Some(false),
+            clip: Some(ClipMode::WrapBoth),
             vblank: Some(false),
             vf_order: Some(false),
             lores_dxy0: Some(LoResDxy0Behavior::default()),
@@ -330,9 +1325,162 @@ impl Default for Quirks {
     }
 }
 
+/// Builds [Quirks] by starting from a platform's preset quirks and then overriding some of them.
+///
+/// Example:
+/// ```
+/// use octopt::{Platform, QuirksBuilder};
+///
+/// let quirks = QuirksBuilder::from_platform(Platform::Vip)
+///     .with_shift(true)
+///     .build();
+/// assert_eq!(quirks.shift, Some(true));
+/// ```
+#[derive(Debug, PartialEq, Clone)]
+pub struct QuirksBuilder {
+    quirks: Quirks,
+}
+
+impl QuirksBuilder {
+    /// Starts from the preset quirks for the given platform, like [`Quirks::from`].
+    pub fn from_platform(platform: Platform) -> Self {
+        Self {
+            quirks: Quirks::from(platform),
+        }
+    }
+
+    /// Sets [`Quirks::shift`].
+    pub fn with_shift(mut self, shift: bool) -> Self {
+        self.quirks.shift = Some(shift);
+        self
+    }
+
+    /// Sets [`Quirks::load_store`].
+    pub fn with_load_store(mut self, load_store: LoadStoreMode) -> Self {
+        self.quirks.load_store = Some(load_store);
+        self
+    }
+
+    /// Sets [`Quirks::jump0`].
+    pub fn with_jump0(mut self, jump0: bool) -> Self {
+        self.quirks.jump0 = Some(jump0);
+        self
+    }
+
+    /// Sets [`Quirks::logic`].
+    pub fn with_logic(mut self, logic: bool) -> Self {
+        self.quirks.logic = Some(logic);
+        self
+    }
+
+    /// Sets [`Quirks::clip`].
+    pub fn with_clip(mut self, clip: ClipMode) -> Self {
+        self.quirks.clip = Some(clip);
+        self
+    }
+
+    /// Sets [`Quirks::vblank`].
+    pub fn with_vblank(mut self, vblank: bool) -> Self {
+        self.quirks.vblank = Some(vblank);
+        self
+    }
+
+    /// Sets [`Quirks::vf_order`].
+    pub fn with_vf_order(mut self, vf_order: bool) -> Self {
+        self.quirks.vf_order = Some(vf_order);
+        self
+    }
+
+    /// Sets [`Quirks::lores_dxy0`].
+    pub fn with_lores_dxy0(mut self, lores_dxy0: LoResDxy0Behavior) -> Self {
+        self.quirks.lores_dxy0 = Some(lores_dxy0);
+        self
+    }
+
+    /// Sets [`Quirks::res_clear`].
+    pub fn with_res_clear(mut self, res_clear: bool) -> Self {
+        self.quirks.res_clear = Some(res_clear);
+        self
+    }
+
+    /// Sets [`Quirks::delay_wrap`].
+    pub fn with_delay_wrap(mut self, delay_wrap: bool) -> Self {
+        self.quirks.delay_wrap = Some(delay_wrap);
+        self
+    }
+
+    /// Sets [`Quirks::hires_collision`].
+    pub fn with_hires_collision(mut self, hires_collision: bool) -> Self {
+        self.quirks.hires_collision = Some(hires_collision);
+        self
+    }
+
+    /// Sets [`Quirks::clip_collision`].
+    pub fn with_clip_collision(mut self, clip_collision: bool) -> Self {
+        self.quirks.clip_collision = Some(clip_collision);
+        self
+    }
+
+    /// Sets [`Quirks::scroll`].
+    pub fn with_scroll(mut self, scroll: bool) -> Self {
+        self.quirks.scroll = Some(scroll);
+        self
+    }
+
+    /// Sets [`Quirks::overflow_i`].
+    pub fn with_overflow_i(mut self, overflow_i: bool) -> Self {
+        self.quirks.overflow_i = Some(overflow_i);
+        self
+    }
+
+    /// Returns the built quirks.
+    pub fn build(self) -> Quirks {
+        self.quirks
+    }
+}
+
+/// The quirks an interpreter actually runs with, where every quirk has been decided, unlike
+/// [Quirks] where each one may be unset. Made by [`Quirks::resolve`].
+///
+/// This can be serialized as a snapshot of an emulator's runtime configuration. Unlike [Quirks],
+/// it has no unset values, and uses the short quirk names, like `shift` and `loadStore`.
+#[derive(Debug, PartialEq, Serialize, Deserialize, Clone, Copy)]
+#[serde(rename_all = "camelCase")]
+#[non_exhaustive]
+pub struct ResolvedQuirks {
+    /// See [`Quirks::shift`].
+    pub shift: bool,
+    /// See [`Quirks::load_store`].
+    pub load_store: LoadStoreMode,
+    /// See [`Quirks::jump0`].
+    pub jump0: bool,
+    /// See [`Quirks::logic`].
+    pub logic: bool,
+    /// See [`Quirks::clip`].
+    pub clip: ClipMode,
+    /// See [`Quirks::vblank`].
+    pub vblank: bool,
+    /// See [`Quirks::vf_order`].
+    pub vf_order: bool,
+    /// See [`Quirks::lores_dxy0`].
+    pub lores_dxy0: LoResDxy0Behavior,
+    /// See [`Quirks::res_clear`].
+    pub res_clear: bool,
+    /// See [`Quirks::delay_wrap`].
+    pub delay_wrap: bool,
+    /// See [`Quirks::hires_collision`].
+    pub hires_collision: bool,
+    /// See [`Quirks::clip_collision`].
+    pub clip_collision: bool,
+    /// See [`Quirks::scroll`].
+    pub scroll: bool,
+    /// See [`Quirks::overflow_i`].
+    pub overflow_i: bool,
+}
+
 /// Represents the different possible behaviors of attempting to draw a sprite with 0 height with
 /// the instruction DXY0 while in lores (low-resolution 64x32) mode.
-#[derive(Display, FromStr, Debug, PartialEq, Serialize, Deserialize)]
+#[derive(Display, FromStr, Debug, Default, PartialEq, Serialize, Deserialize, Copy, Clone)]
 #[serde(rename_all = "snake_case")]
 #[display(style = "snake_case")]
 pub enum LoResDxy0Behavior {
@@ -342,18 +1490,112 @@ pub enum LoResDxy0Behavior {
     TallSprite,
     /// Draw a 16x16 sprite, ie. the same behavior as in hires (high-resolution 128x64 SUPER-CHIP
     /// XO-CHIP) mode (Octo behavior)
+    #[default]
     BigSprite,
 }
 
-impl Default for LoResDxy0Behavior {
-    fn default() -> Self {
-        Self::BigSprite
+// The options Octo sets for a new game, exactly as it exports them. The golden file is shared with
+// the tests, which check it against Octo's own defaults.
+const OCTO_NEW_GAME: &str = include_str!("../tests/golden/octo_new_game.json");
+
+/// Represents the different possible behaviors of sprites drawn partly outside the screen.
+#[derive(Display, FromStr, Debug, Default, PartialEq, Copy, Clone)]
+#[display(style = "snake_case")]
+pub enum ClipMode {
+    /// Sprites wrap around to the opposite edge both horizontally and vertically (Octo behavior)
+    #[default]
+    WrapBoth,
+    /// Sprites are clipped both horizontally and vertically (original, CHIP-48 and SUPER-CHIP
+    /// behavior)
+    ClipBoth,
+    /// Sprites are clipped horizontally, but wrap around vertically
+    #[display("clip_x_wrap_y")]
+    ClipXWrapY,
+    /// Sprites wrap around horizontally, but are clipped vertically
+    #[display("wrap_x_clip_y")]
+    WrapXClipY,
+}
+
+/// WrapBoth and ClipBoth are serialized as `false` and `true`, like Octo's `clipQuirks`, and the
+/// other modes as strings, like `clip_x_wrap_y`.
+impl Serialize for ClipMode {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        match self.to_bool() {
+            Some(value) => serializer.serialize_bool(value),
+            None => serializer.serialize_str(&self.to_string()),
+        }
     }
 }
 
+/// Deserializes a boolean, 0 or 1 as WrapBoth or ClipBoth, or any mode from its name.
+impl<'de> Deserialize<'de> for ClipMode {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        BoolOrU8OrStr::deserialize(deserializer)?.into_mode()
+    }
+}
+
+impl BoolOrMode for ClipMode {
+    const FALSE: Self = ClipMode::WrapBoth;
+    const TRUE: Self = ClipMode::ClipBoth;
+    const EXPECTING: &'static str = "zero, one or a clip mode";
+}
+
+/// Represents the different possible behaviors of the I register after the load and store
+/// instructions FX55 and FX65.
+#[derive(Display, FromStr, Debug, Default, PartialEq, Copy, Clone)]
+#[display(style = "snake_case")]
+pub enum LoadStoreMode {
+    /// I is incremented for each register loaded or stored, ie. by X+1 (original behavior)
+    #[default]
+    #[display("increment_by_x_plus_1")]
+    IncrementByXPlus1,
+    /// I is incremented by X, one less than the number of registers loaded or stored (some
+    /// CHIP-48 era interpreters)
+    #[display("increment_by_x")]
+    IncrementByX,
+    /// I is left unchanged (SUPER-CHIP behavior)
+    Unchanged,
+}
+
+/// IncrementByXPlus1 and Unchanged are serialized as `false` and `true`, like Octo's
+/// `loadStoreQuirks`, and IncrementByX as the string `increment_by_x`.
+impl Serialize for LoadStoreMode {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        match self.to_bool() {
+            Some(value) => serializer.serialize_bool(value),
+            None => serializer.serialize_str(&self.to_string()),
+        }
+    }
+}
+
+/// Deserializes a boolean, 0 or 1 as IncrementByXPlus1 or Unchanged, or any mode from its name.
+impl<'de> Deserialize<'de> for LoadStoreMode {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        BoolOrU8OrStr::deserialize(deserializer)?.into_mode()
+    }
+}
+
+impl BoolOrMode for LoadStoreMode {
+    const FALSE: Self = LoadStoreMode::IncrementByXPlus1;
+    const TRUE: Self = LoadStoreMode::Unchanged;
+    const EXPECTING: &'static str = "zero, one or a load/store mode";
+}
+
 /// Representation of Octo options.
 #[skip_serializing_none]
-#[derive(Debug, PartialEq, Serialize, Deserialize)]
+#[derive(Debug, PartialEq, Serialize, Deserialize, Clone)]
 #[serde(rename_all = "camelCase")]
 #[non_exhaustive]
 pub struct Options {
@@ -365,6 +1607,9 @@ pub struct Options {
     /// * 7–15 (approximate speed of the original interpreter for the COSMAC VIP)
     /// * 20–30 (approximate speed of the SUPER-CHIP interpreters for the HP 48 calculators)
     /// * 10000 (Octo's "Ludicrous speed" setting)
+    ///
+    /// When deserializing, a tickrate given as a float, like `20.0`, is rounded to the nearest
+    /// integer, with halves rounded up, so `20.5` becomes 21.
     #[serde(default, deserialize_with = "some_u16_from_int_or_str")]
     pub tickrate: Option<u16>,
     /// The maximum amount of virtual memory, in bytes, that is available to the program. If the CHIP-8 program is
@@ -385,7 +1630,16 @@ pub struct Options {
     ///
     /// Other values might be used for games for more obscure platforms, games that were designed
     /// to run on a COSMAC VIP with only 2K RAM, etc.
-    #[serde(default, deserialize_with = "some_u16_from_int_or_str")]
+    ///
+    /// This is called `maxSize` in JSON and `core.max_rom` in INI, but either format will accept
+    /// the other's name (`max_rom` in JSON, `core.maxSize` in INI) when deserializing. Like the
+    /// tickrate, a size given as a float is rounded to the nearest integer, with halves rounded
+    /// up.
+    #[serde(
+        default,
+        alias = "max_rom",
+        deserialize_with = "some_u16_from_int_or_str"
+    )]
     pub max_size: Option<u16>, // {3216, 3583, 3584, 65024}
     /// The orientation of the display.
     #[serde(default)]
@@ -393,6 +1647,12 @@ pub struct Options {
     /// The font style expected by the game.
     #[serde(default)]
     pub font_style: Font,
+    /// The font style used for the big digits, if it differs from `font_style`. Some interpreters
+    /// combine the small digits from one font with the big digits from another, like SUPER-CHIP's
+    /// small digits with Octo's big digits.
+    ///
+    /// Not supported by the INI format.
+    pub font_big_style: Option<Font>,
     /// The touch controls this game supports.
     #[serde(default)]
     pub touch_input_mode: TouchMode, // OCTO_TOUCH_...
@@ -403,18 +1663,62 @@ pub struct Options {
     /// Common values:
     /// * 512 (original interpreter for the COSMAC VIP, DREAM 6800, HP 48, etc)
     /// * 1536 (interpreter for the ETI-660)
+    ///
+    /// When deserializing, the address can also be given as a hexadecimal string, like `"0x200"`,
+    /// and an address given as a float is rounded to the nearest integer, with halves rounded up.
     #[serde(default, deserialize_with = "some_u16_from_int_or_str")]
     pub start_address: Option<u16>,
+    /// The number of bitplanes the game draws to. XO-CHIP has 2 bitplanes, which is assumed if
+    /// this is `None`, but some extensions support up to 4. Must be between 1 and 4.
+    ///
+    /// Not supported by the INI format.
+    #[serde(default, deserialize_with = "some_plane_count")]
+    pub plane_count: Option<u8>,
+    /// The pitch of the sound buzzer, in Hz, if the game expects a specific one. Most
+    /// interpreters use a fixed pitch somewhere around 400–500 Hz, but it varied between
+    /// platforms. This isn't an Octo option.
+    ///
+    /// When deserializing, a fractional pitch is rounded to the nearest Hz, with halves rounded
+    /// up, so `440.5` becomes 441.
+    ///
+    /// Not supported by the INI format.
+    #[serde(default, deserialize_with = "some_u16_from_int_or_str")]
+    pub buzzer_pitch: Option<u16>,
+    /// The memory address where the game expects the interpreter's font, for games that read the
+    /// font data directly instead of only using FX29. Most interpreters place it at `0x50`
+    /// (80), which is assumed if this is `None`; see [`Options::effective_font_address`]. This
+    /// isn't an Octo option.
+    ///
+    /// When deserializing, the address can be given as a hexadecimal string, like `"0x50"`, or
+    /// as a float, which is rounded to the nearest integer with halves rounded up.
+    ///
+    /// Not supported by the INI format.
+    #[serde(default, deserialize_with = "some_u16_from_int_or_str")]
+    pub font_address: Option<u16>,
+    /// Font data supplied by the game itself, for games with their own font. If this is set, it's
+    /// used instead of `font_style` and `font_big_style`; see [`Options::get_font_data`]. This
+    /// isn't an Octo option.
+    ///
+    /// Not supported by the INI format.
+    pub custom_font: Option<CustomFont>,
 
     /// Custom colors this game would like to use, if possible. It's not important for a CHIP-8
     /// interpreter to support custom colors although not doing so might impact the creator's
     /// artistic vision, especially for XO-CHIP games that use more than two colors.
-    #[serde(flatten)]
+    ///
+    /// Besides Octo's format, where each color is a top-level key like `fillColor`, this also
+    /// accepts colors nested in a `colors` object, with keys like `fill`. If both are present,
+    /// the nested colors take precedence.
+    #[serde(flatten, deserialize_with = "flat_or_nested_colors")]
     pub colors: Colors,
 
     /// Specific behaviors this game expects from the interpreter in order to run properly. See
     /// [`OctoQuirks`] for specifics.
-    #[serde(flatten)]
+    ///
+    /// Besides Octo's format, where each quirk is a top-level key like `shiftQuirks`, this also
+    /// accepts quirks nested in a `quirks` object, with keys like `shift`. If both are present,
+    /// the nested quirks take precedence.
+    #[serde(flatten, deserialize_with = "flat_or_nested_quirks")]
     pub quirks: Quirks,
 }
 
@@ -426,6 +1730,11 @@ impl Default for Options {
             max_size: Some(65024),
             screen_rotation: ScreenRotation::default(),
             font_style: Font::default(),
+            font_big_style: None,
+            plane_count: None,
+            buzzer_pitch: None,
+            font_address: None,
+            custom_font: None,
             touch_input_mode: TouchMode::default(),
             start_address: Some(0x200),
             colors: Colors::default(),
@@ -434,13 +1743,196 @@ impl Default for Options {
     }
 }
 
+/// Identifies a single field of [Options], including the fields of its [Colors] and [Quirks], for
+/// code that needs to address fields generically, like a configuration editor.
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
+#[non_exhaustive]
+pub enum OptionsField {
+    /// [`Options::tickrate`]
+    Tickrate,
+    /// [`Options::max_size`]
+    MaxSize,
+    /// [`Options::screen_rotation`]
+    ScreenRotation,
+    /// [`Options::font_style`]
+    FontStyle,
+    /// [`Options::font_big_style`]
+    FontBigStyle,
+    /// [`Options::touch_input_mode`]
+    TouchInputMode,
+    /// [`Options::start_address`]
+    StartAddress,
+    /// [`Options::plane_count`]
+    PlaneCount,
+    /// [`Options::buzzer_pitch`]
+    BuzzerPitch,
+    /// [`Options::font_address`]
+    FontAddress,
+    /// [`Options::custom_font`]
+    CustomFont,
+    /// [`Colors::fill_color`]
+    FillColor,
+    /// [`Colors::fill_color2`]
+    FillColor2,
+    /// [`Colors::blend_color`]
+    BlendColor,
+    /// [`Colors::background_color`]
+    BackgroundColor,
+    /// [`Colors::buzz_color`]
+    BuzzColor,
+    /// [`Colors::quiet_color`]
+    QuietColor,
+    /// [`Colors::background_transparent`]
+    BackgroundTransparent,
+    /// [`Quirks::shift`]
+    Shift,
+    /// [`Quirks::load_store`]
+    LoadStore,
+    /// [`Quirks::jump0`]
+    Jump0,
+    /// [`Quirks::logic`]
+    Logic,
+    /// [`Quirks::clip`]
+    Clip,
+    /// [`Quirks::vblank`]
+    Vblank,
+    /// [`Quirks::vf_order`]
+    VfOrder,
+    /// [`Quirks::lores_dxy0`]
+    LoresDxy0,
+    /// [`Quirks::res_clear`]
+    ResClear,
+    /// [`Quirks::delay_wrap`]
+    DelayWrap,
+    /// [`Quirks::hires_collision`]
+    HiresCollision,
+    /// [`Quirks::clip_collision`]
+    ClipCollision,
+    /// [`Quirks::scroll`]
+    Scroll,
+    /// [`Quirks::overflow_i`]
+    OverflowI,
+}
+
+impl OptionsField {
+    /// Returns the field with the given JSON key, like `tickrate` or `fillColor`, or `None` if
+    /// the key isn't an option. The other names that are accepted when deserializing, like
+    /// `max_rom` for `maxSize`, are recognized too. Quirks can be given by Octo's name, like
+    /// `shiftQuirks`, or by their short name, like `shift`, in any case.
+    pub fn from_json_key(key: &str) -> Option<OptionsField> {
+        Some(match key {
+            "tickrate" => OptionsField::Tickrate,
+            "maxSize" | "max_rom" => OptionsField::MaxSize,
+            "screenRotation" => OptionsField::ScreenRotation,
+            "fontStyle" => OptionsField::FontStyle,
+            "fontBigStyle" => OptionsField::FontBigStyle,
+            "touchInputMode" => OptionsField::TouchInputMode,
+            "startAddress" => OptionsField::StartAddress,
+            "planeCount" => OptionsField::PlaneCount,
+            "buzzerPitch" => OptionsField::BuzzerPitch,
+            "fontAddress" => OptionsField::FontAddress,
+            "customFont" => OptionsField::CustomFont,
+            "fillColor" => OptionsField::FillColor,
+            "fillColor2" => OptionsField::FillColor2,
+            "blendColor" => OptionsField::BlendColor,
+            "backgroundColor" => OptionsField::BackgroundColor,
+            "buzzColor" | "buzzer" | "buzzerColor" => OptionsField::BuzzColor,
+            "quietColor" | "silence" | "silenceColor" => OptionsField::QuietColor,
+            "backgroundTransparent" => OptionsField::BackgroundTransparent,
+            // The short names have a few aliases of their own
+            key if key.eq_ignore_ascii_case("jump") => OptionsField::Jump0,
+            key => {
+                let (octo_name, _) = short_quirks::QUIRK_NAMES.iter().find(|(octo, short)| {
+                    octo.eq_ignore_ascii_case(key) || short.eq_ignore_ascii_case(key)
+                })?;
+                match *octo_name {
+                    "shiftQuirks" => OptionsField::Shift,
+                    "loadStoreQuirks" => OptionsField::LoadStore,
+                    "jumpQuirks" => OptionsField::Jump0,
+                    "logicQuirks" => OptionsField::Logic,
+                    "clipQuirks" => OptionsField::Clip,
+                    "vBlankQuirks" => OptionsField::Vblank,
+                    "vfOrderQuirks" => OptionsField::VfOrder,
+                    "loresDXY0Quirks" => OptionsField::LoresDxy0,
+                    "resClearQuirks" => OptionsField::ResClear,
+                    "delayWrapQuirks" => OptionsField::DelayWrap,
+                    "hiresCollisionQuirks" => OptionsField::HiresCollision,
+                    "clipCollisionQuirks" => OptionsField::ClipCollision,
+                    "scrollQuirks" => OptionsField::Scroll,
+                    _ => OptionsField::OverflowI,
+                }
+            }
+        })
+    }
+
+    // The key this field is serialized with in JSON
+    fn json_key(self) -> &'static str {
+        match self {
+            OptionsField::Tickrate => "tickrate",
+            OptionsField::MaxSize => "maxSize",
+            OptionsField::ScreenRotation => "screenRotation",
+            OptionsField::FontStyle => "fontStyle",
+            OptionsField::FontBigStyle => "fontBigStyle",
+            OptionsField::TouchInputMode => "touchInputMode",
+            OptionsField::StartAddress => "startAddress",
+            OptionsField::PlaneCount => "planeCount",
+            OptionsField::BuzzerPitch => "buzzerPitch",
+            OptionsField::FontAddress => "fontAddress",
+            OptionsField::CustomFont => "customFont",
+            OptionsField::FillColor => "fillColor",
+            OptionsField::FillColor2 => "fillColor2",
+            OptionsField::BlendColor => "blendColor",
+            OptionsField::BackgroundColor => "backgroundColor",
+            OptionsField::BuzzColor => "buzzColor",
+            OptionsField::QuietColor => "quietColor",
+            OptionsField::BackgroundTransparent => "backgroundTransparent",
+            OptionsField::Shift => "shiftQuirks",
+            OptionsField::LoadStore => "loadStoreQuirks",
+            OptionsField::Jump0 => "jumpQuirks",
+            OptionsField::Logic => "logicQuirks",
+            OptionsField::Clip => "clipQuirks",
+            OptionsField::Vblank => "vBlankQuirks",
+            OptionsField::VfOrder => "vfOrderQuirks",
+            OptionsField::LoresDxy0 => "loresDXY0Quirks",
+            OptionsField::ResClear => "resClearQuirks",
+            OptionsField::DelayWrap => "delayWrapQuirks",
+            OptionsField::HiresCollision => "hiresCollisionQuirks",
+            OptionsField::ClipCollision => "clipCollisionQuirks",
+            OptionsField::Scroll => "scrollQuirks",
+            OptionsField::OverflowI => "overflowIQuirks",
+        }
+    }
+
+    // Whether this is one of the fields of Quirks
+    fn is_quirk(self) -> bool {
+        matches!(
+            self,
+            OptionsField::Shift
+                | OptionsField::LoadStore
+                | OptionsField::Jump0
+                | OptionsField::Logic
+                | OptionsField::Clip
+                | OptionsField::Vblank
+                | OptionsField::VfOrder
+                | OptionsField::LoresDxy0
+                | OptionsField::ResClear
+                | OptionsField::DelayWrap
+                | OptionsField::HiresCollision
+                | OptionsField::ClipCollision
+                | OptionsField::Scroll
+                | OptionsField::OverflowI
+        )
+    }
+}
+
 /// Possible orientations of the display. Note that this should only affect the visual
 /// representation of the screen; draw operations still act as if the screen rotation is 0. Only
 /// used by some Octo games.
-#[derive(Serialize_repr, Deserialize_repr, PartialEq, Debug)]
+#[derive(Serialize_repr, Deserialize_repr, PartialEq, Debug, Default, Copy, Clone)]
 #[repr(u16)]
 pub enum ScreenRotation {
     /// Normal landscape screen display, used by 99.9999% of CHIP-8 games
+    #[default]
     Normal = 0,
     /// Portrait screen display, ie. a normal screen rotated 90 degrees clockwise
     ClockWise = 90,
@@ -450,35 +1942,955 @@ pub enum ScreenRotation {
     CounterClockWise = 270,
 }
 
-impl Default for ScreenRotation {
-    fn default() -> Self {
-        Self::Normal
+impl ScreenRotation {
+    /// Transforms the coordinates of a logical pixel, ie. a pixel on the unrotated CHIP-8 screen
+    /// with the given `width` and `height`, into the coordinates of that pixel on the rotated
+    /// display. Returns `None` if the pixel isn't on the screen.
+    ///
+    /// Note that the rotated display is `height` pixels wide and `width` pixels tall if the
+    /// rotation is [`ClockWise`](Self::ClockWise) or [`CounterClockWise`](Self::CounterClockWise).
+    ///
+    /// Example:
+    /// ```
+    /// use octopt::ScreenRotation;
+    ///
+    /// assert_eq!(ScreenRotation::ClockWise.transform(0, 0, 64, 32), Some((31, 0)));
+    /// assert_eq!(ScreenRotation::ClockWise.transform(64, 0, 64, 32), None);
+    /// ```
+    pub fn transform(&self, x: u16, y: u16, width: u16, height: u16) -> Option<(u16, u16)> {
+        let flipped_x = width.checked_sub(1)?.checked_sub(x)?;
+        let flipped_y = height.checked_sub(1)?.checked_sub(y)?;
+        Some(match self {
+            ScreenRotation::Normal => (x, y),
+            ScreenRotation::ClockWise => (flipped_y, x),
+            ScreenRotation::UpsideDown => (flipped_x, flipped_y),
+            ScreenRotation::CounterClockWise => (y, flipped_x),
+        })
+    }
+
+    /// Which parts of the emulation the screen rotation applies to. Currently this is always
+    /// [`RotationScope::Display`], as in Octo.
+    pub fn scope(&self) -> RotationScope {
+        RotationScope::default()
+    }
+
+    /// Whether draw operations should act on rotated coordinates. This is always false, since
+    /// rotation only affects how the screen is displayed; see [`ScreenRotation::scope`].
+    ///
+    /// Example:
+    /// ```
+    /// use octopt::ScreenRotation;
+    ///
+    /// assert!(!ScreenRotation::ClockWise.affects_drawing());
+    /// ```
+    pub fn affects_drawing(&self) -> bool {
+        self.scope() == RotationScope::Drawing
+    }
+}
+
+/// What a [ScreenRotation] applies to.
+#[derive(PartialEq, Eq, Debug, Default, Copy, Clone)]
+#[non_exhaustive]
+pub enum RotationScope {
+    /// Only the visual representation of the screen is rotated, and draw operations act as if
+    /// the screen rotation is 0. This is how Octo does it.
+    #[default]
+    Display,
+    /// Draw operations act on the rotated screen, so that eg. coordinate (0, 0) is in the top
+    /// left corner of the rotated display. No known interpreter does this.
+    Drawing,
+}
+
+/// Deserializes Options from a JSON string.
+///
+/// This format is used by Octo in Octocarts and HTML exports, as well as the Chip-8 Archive.
+/// A leading UTF-8 byte order mark, which some Windows editors add, is ignored.
+impl FromStr for Options {
+    type Err = ParseError;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        serde_json::from_str(strip_bom(s)).map_err(ParseError::Json)
+    }
+}
+
+// Removes a leading UTF-8 byte order mark, which serde_json doesn't accept.
+pub(crate) fn strip_bom(s: &str) -> &str {
+    s.strip_prefix('\u{feff}').unwrap_or(s)
+}
+
+impl Options {
+    /// Deserializes Options from JSON read from `reader`, but gives up without parsing anything
+    /// if the input is larger than `max_bytes`. Useful for untrusted input, like uploads.
+    ///
+    /// # Errors
+    ///
+    /// Returns an `Err` if reading failed, if the input was too large, or if deserialization
+    /// from the JSON failed.
+    pub fn from_json_reader_limited<R: Read>(
+        reader: R,
+        max_bytes: usize,
+    ) -> Result<Self, ParseError> {
+        let mut bytes = Vec::new();
+        let limit = u64::try_from(max_bytes)
+            .unwrap_or(u64::MAX)
+            .saturating_add(1);
+        reader
+            .take(limit)
+            .read_to_end(&mut bytes)
+            .map_err(ParseError::Io)?;
+        if bytes.len() > max_bytes {
+            return Err(ParseError::TooLarge(max_bytes));
+        }
+        let bytes = bytes.strip_prefix(b"\xef\xbb\xbf").unwrap_or(&bytes);
+        serde_json::from_slice(bytes).map_err(ParseError::Json)
+    }
+
+    /// Reads Options from the contents of a file in any of the formats this crate knows, by
+    /// looking at the bytes to see which format it is:
+    /// * JSON, either Options on their own or an object with the Options under `options`, like
+    ///   the JSON that Octo stores in its cartridges
+    /// * INI, as used by C-Octo
+    /// * Octocarts, the GIF images Octo saves games in, with the program and options embedded
+    ///
+    /// # Errors
+    ///
+    /// Returns an `Err` if the format isn't supported, if a GIF image isn't an Octocart, or if
+    /// deserialization failed.
+    pub fn try_from_cart_bytes(bytes: &[u8]) -> Result<Self, ParseError> {
+        let payload;
+        let bytes = if bytes.starts_with(b"GIF87a") || bytes.starts_with(b"GIF89a") {
+            payload = octocart::read_payload(bytes).ok_or(ParseError::UnsupportedFormat)?;
+            &payload[..]
+        } else {
+            bytes
+        };
+        let text = std::str::from_utf8(bytes).map_err(|_| ParseError::UnsupportedFormat)?;
+        let text = strip_bom(text);
+        if text.trim_start().starts_with('{') {
+            let mut json: serde_json::Value =
+                serde_json::from_str(text).map_err(ParseError::Json)?;
+            if json
+                .get("options")
+                .is_some_and(serde_json::Value::is_object)
+            {
+                json = json["options"].take();
+            }
+            serde_json::from_value(json).map_err(ParseError::Json)
+        } else {
+            Self::from_ini(text).map_err(ParseError::Ini)
+        }
+    }
+
+    /// Deserializes Options from an INI string.
+    ///
+    /// # Errors
+    ///
+    /// Returns an `Err` if deserialization from the INI failed.
+    pub fn from_ini(s: &str) -> Result<Self, serde_ini::de::Error> {
+        Ok(Self::from(OptionsIni::from_str(s)?))
+    }
+
+    /// Serializes Options to an INI string.
+    pub fn to_ini(self) -> String {
+        OptionsIni::to_string(&OptionsIni::from(self))
+    }
+
+    /// Reads Options from the header of an Octo `.8o` source file, ie. the comments at the very
+    /// top of the file, before any code. Each comment can hold a directive consisting of a key
+    /// from the JSON format and a value, separated by a space, `=` or `:`, like `# tickrate 20`
+    /// or `# shiftQuirks: 1`.
+    ///
+    /// Comments that aren't directives, and directives with unknown keys or invalid values, are
+    /// ignored. Options that aren't set in the header are `None` or their default values.
+    ///
+    /// Example:
+    /// ```
+    /// use octopt::{ClipMode, Options};
+    ///
+    /// let source = "# My Game\n# tickrate 20\n# clipQuirks = true\n: main\n  loop again\n";
+    /// let options = Options::from_8o_header(source);
+    /// assert_eq!(options.tickrate, Some(20));
+    /// assert_eq!(options.quirks.clip, Some(ClipMode::ClipBoth));
+    /// ```
+    pub fn from_8o_header(source: &str) -> Self {
+        // Options with nothing set, which the directives are applied to one by one
+        let mut options = Self::deserialize(serde_json::json!({})).unwrap_or_default();
+        let comments = source
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty())
+            .map_while(|line| line.strip_prefix('#'));
+        for comment in comments {
+            let Some((key, value)) = comment
+                .trim()
+                .split_once(|c: char| c.is_whitespace() || c == '=' || c == ':')
+            else {
+                continue;
+            };
+            let value =
+                value.trim_start_matches(|c: char| c.is_whitespace() || c == '=' || c == ':');
+            if let Some(field) = OptionsField::from_json_key(key) {
+                // Invalid values are ignored, leaving the option as it was
+                let _ = options.set_field_str(field, value);
+            }
+        }
+        options
+    }
+
+    /// Reads Options from a string of core options in the style of libretro and SDL frontends,
+    /// like `tickrate=20;font=schip;shift=enabled`, on top of the [default](Options::default)
+    /// Options.
+    ///
+    /// Keys are JSON keys, like `tickrate` or `shiftQuirks`, or short quirk names, like `shift`;
+    /// `font` is accepted as a shorthand for `fontStyle`. Unknown keys are ignored. Besides the
+    /// values the JSON format accepts, boolean values can be `enabled` or `disabled`, like
+    /// libretro's.
+    ///
+    /// # Errors
+    ///
+    /// Returns an `Err` if an entry isn't of the form `key=value`, or if a value isn't valid for
+    /// its key.
+    pub fn from_core_option_string(s: &str) -> Result<Self, SetError> {
+        let mut options = Self::default();
+        for entry in s
+            .split(';')
+            .map(str::trim)
+            .filter(|entry| !entry.is_empty())
+        {
+            let (key, value) = entry.split_once('=').ok_or(SetError::MissingEquals)?;
+            let key = match key.trim() {
+                "font" => "fontStyle",
+                key => key,
+            };
+            let value = match value.trim() {
+                "enabled" => "true",
+                "disabled" => "false",
+                value => value,
+            };
+            if let Some(field) = OptionsField::from_json_key(key) {
+                options.set_field_str(field, value)?;
+            }
+        }
+        Ok(options)
+    }
+
+    /// Sets a single quirk from a string of the form `key=value`, for example from a debug
+    /// console. The key can be either the quirk's JSON key, like `shiftQuirks`, or its short
+    /// name, like `shift`, in any case. The value can be `0`, `1`, `true` or `false`, one of the
+    /// mode names for quirks with more than two behaviors (like `big_sprite` for
+    /// `loresDXY0Quirks`), or `null` to unset the quirk.
+    ///
+    /// # Errors
+    ///
+    /// Returns an `Err` if the string isn't of the form `key=value`, if the key isn't a known
+    /// quirk, or if the value isn't valid for that quirk.
+    pub fn apply_quirk_str(&mut self, s: &str) -> Result<(), SetError> {
+        let (key, value) = s.split_once('=').ok_or(SetError::MissingEquals)?;
+        let key = key.trim();
+        match OptionsField::from_json_key(key) {
+            Some(field) if field.is_quirk() => self.set_field_str(field, value),
+            _ => Err(SetError::UnknownKey(key.to_string())),
+        }
+    }
+
+    /// Overrides options with environment variables whose names start with the given prefix
+    /// followed by an underscore, which is useful for tuning emulators running in containers.
+    ///
+    /// The rest of the name is the option's JSON key in SCREAMING_SNAKE_CASE, so with the prefix
+    /// `OCTO`, `OCTO_TICKRATE` sets the tickrate and `OCTO_FILL_COLOR` sets the fill color.
+    /// Quirks use their short names with an extra `QUIRK_` prefix, like `OCTO_QUIRK_SHIFT`.
+    /// Variables that don't match an option are ignored.
+    ///
+    /// # Errors
+    ///
+    /// Returns an `Err` if a matching variable has a value that isn't valid for its option, or
+    /// that isn't valid Unicode, in which case options from other variables may already have been
+    /// applied.
+    pub fn apply_env(&mut self, prefix: &str) -> Result<(), SetError> {
+        let prefix = format!("{}_", prefix);
+        for (name, value) in std::env::vars_os() {
+            // Other variables may not be valid Unicode, which is fine since they aren't ours
+            let Some(name) = name.to_str().and_then(|name| name.strip_prefix(&prefix)) else {
+                continue;
+            };
+            let value = value
+                .into_string()
+                .map_err(|value| SetError::InvalidValue(value.to_string_lossy().into_owned()))?;
+            let result = match name.strip_prefix("QUIRK_") {
+                Some(quirk) => {
+                    self.apply_quirk_str(&format!("{}={}", camel_case_from_env(quirk), value))
+                }
+                None => self.apply_option_str(&camel_case_from_env(name), &value),
+            };
+            match result {
+                Err(SetError::UnknownKey(_)) | Ok(()) => {}
+                Err(e) => return Err(e),
+            }
+        }
+        Ok(())
+    }
+
+    // Sets a single option that isn't a quirk, given its JSON key and its value as plain text.
+    fn apply_option_str(&mut self, key: &str, value: &str) -> Result<(), SetError> {
+        match OptionsField::from_json_key(key) {
+            Some(field) if !field.is_quirk() => self.set_field_str(field, value),
+            _ => Err(SetError::UnknownKey(key.to_string())),
+        }
+    }
+
+    // Sets a single field, given its value as plain text, which is parsed the same way as the
+    // field's value in JSON. `null` unsets fields that can be unset.
+    fn set_field_str(&mut self, field: OptionsField, value: &str) -> Result<(), SetError> {
+        let value = value.trim();
+        let invalid = || SetError::InvalidValue(value.to_string());
+        // Numbers and booleans are written without quotes in JSON, while strings are quoted
+        let number = json_value_from_str(value);
+        let text = match number {
+            serde_json::Value::Null => serde_json::Value::Null,
+            _ => serde_json::Value::from(value),
+        };
+        match field {
+            OptionsField::Tickrate => {
+                self.tickrate = parse_optional(value, number, some_u16_from_int_or_str)?;
+            }
+            OptionsField::MaxSize => {
+                self.max_size = parse_optional(value, number, some_u16_from_int_or_str)?;
+            }
+            OptionsField::ScreenRotation => {
+                self.screen_rotation =
+                    ScreenRotation::deserialize(number).map_err(|_| invalid())?;
+            }
+            OptionsField::FontStyle => {
+                self.font_style = Font::deserialize(text).map_err(|_| invalid())?;
+            }
+            OptionsField::FontBigStyle => {
+                self.font_big_style = parse_optional(value, text, Option::<Font>::deserialize)?;
+            }
+            OptionsField::TouchInputMode => {
+                self.touch_input_mode = TouchMode::deserialize(text).map_err(|_| invalid())?;
+            }
+            OptionsField::StartAddress => {
+                self.start_address = parse_optional(value, number, some_u16_from_int_or_str)?;
+            }
+            OptionsField::PlaneCount => {
+                self.plane_count = parse_optional(value, number, some_plane_count)?;
+            }
+            OptionsField::BuzzerPitch => {
+                self.buzzer_pitch = parse_optional(value, number, some_u16_from_int_or_str)?;
+            }
+            OptionsField::FontAddress => {
+                self.font_address = parse_optional(value, number, some_u16_from_int_or_str)?;
+            }
+            OptionsField::CustomFont => {
+                let json = serde_json::from_str(value).map_err(|_| invalid())?;
+                self.custom_font = parse_optional(value, json, Option::<CustomFont>::deserialize)?;
+            }
+            OptionsField::FillColor => {
+                self.colors.fill_color = parse_optional(value, text, Option::<Color>::deserialize)?;
+            }
+            OptionsField::FillColor2 => {
+                self.colors.fill_color2 =
+                    parse_optional(value, text, Option::<Color>::deserialize)?;
+            }
+            OptionsField::BlendColor => {
+                self.colors.blend_color =
+                    parse_optional(value, text, Option::<Color>::deserialize)?;
+            }
+            OptionsField::BackgroundColor => {
+                self.colors.background_color =
+                    parse_optional(value, text, Option::<Color>::deserialize)?;
+            }
+            OptionsField::BuzzColor => {
+                self.colors.buzz_color = parse_optional(value, text, Option::<Color>::deserialize)?;
+            }
+            OptionsField::QuietColor => {
+                self.colors.quiet_color =
+                    parse_optional(value, text, Option::<Color>::deserialize)?;
+            }
+            OptionsField::BackgroundTransparent => {
+                self.colors.background_transparent =
+                    parse_optional(value, number, Option::<bool>::deserialize)?;
+            }
+            OptionsField::Shift => {
+                self.quirks.shift = parse_optional(value, number, some_bool_from_int)?;
+            }
+            OptionsField::LoadStore => {
+                self.quirks.load_store =
+                    parse_optional(value, number, Option::<LoadStoreMode>::deserialize)?;
+            }
+            OptionsField::Jump0 => {
+                self.quirks.jump0 = parse_optional(value, number, some_bool_from_int)?;
+            }
+            OptionsField::Logic => {
+                self.quirks.logic = parse_optional(value, number, some_bool_from_int)?;
+            }
+            OptionsField::Clip => {
+                self.quirks.clip = parse_optional(value, number, Option::<ClipMode>::deserialize)?;
+            }
+            OptionsField::Vblank => {
+                self.quirks.vblank = parse_optional(value, number, some_bool_from_int)?;
+            }
+            OptionsField::VfOrder => {
+                self.quirks.vf_order = parse_optional(value, number, some_bool_from_int)?;
+            }
+            OptionsField::LoresDxy0 => {
+                self.quirks.lores_dxy0 =
+                    parse_optional(value, text, Option::<LoResDxy0Behavior>::deserialize)?;
+            }
+            OptionsField::ResClear => {
+                self.quirks.res_clear = parse_optional(value, number, some_bool_from_int)?;
+            }
+            OptionsField::DelayWrap => {
+                self.quirks.delay_wrap = parse_optional(value, number, some_bool_from_int)?;
+            }
+            OptionsField::HiresCollision => {
+                self.quirks.hires_collision = parse_optional(value, number, some_bool_from_int)?;
+            }
+            OptionsField::ClipCollision => {
+                self.quirks.clip_collision = parse_optional(value, number, some_bool_from_int)?;
+            }
+            OptionsField::Scroll => {
+                self.quirks.scroll = parse_optional(value, number, some_bool_from_int)?;
+            }
+            OptionsField::OverflowI => {
+                self.quirks.overflow_i = parse_optional(value, number, some_bool_from_int)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Deserializes the Options of every program in a CHIP-8 Community Archive `programs.json`
+    /// string, and returns them in a map keyed by each program's key in the archive.
+    ///
+    /// Programs whose options can't be deserialized are skipped. If a program has a `platform`
+    /// that matches a [Platform], quirks that aren't set are taken from that platform.
+    ///
+    /// # Errors
+    ///
+    /// Returns an `Err` if the string isn't a JSON object.
+    pub fn parse_archive(json: &str) -> Result<HashMap<String, Self>, ParseError> {
+        let programs: HashMap<String, serde_json::Value> =
+            serde_json::from_str(json).map_err(ParseError::Json)?;
+        Ok(programs
+            .into_iter()
+            .filter_map(|(key, mut program)| {
+                let mut options: Self =
+                    Self::deserialize(program.get_mut("options")?.take()).ok()?;
+                if let Some(platform) = program
+                    .get("platform")
+                    .and_then(|platform| Platform::deserialize(platform).ok())
+                {
+                    options.quirks = options.quirks.or(Quirks::from(platform));
+                }
+                Some((key, options))
+            })
+            .collect())
+    }
+
+    /// Writes many sets of Options as newline-delimited JSON (NDJSON), one line per set, where each
+    /// line is an object with the set's `key` and its `options`, like
+    /// `{"key":"my_game","options":{...}}`. Useful for exporting a whole library in bulk.
+    ///
+    /// # Errors
+    ///
+    /// Returns an `Err` if writing failed.
+    pub fn write_ndjson<W: Write>(configs: &[(String, Options)], mut writer: W) -> io::Result<()> {
+        for (key, options) in configs {
+            serde_json::to_writer(
+                &mut writer,
+                &serde_json::json!({ "key": key, "options": options }),
+            )?;
+            writer.write_all(b"\n")?;
+        }
+        Ok(())
+    }
+
+    /// Flattens these Options into a map of plain strings, for storing them somewhere that can't
+    /// hold nested structures. The keys are the JSON keys, where colors are prefixed with
+    /// `colors.` and quirks with `quirks.`, like `quirks.shiftQuirks`. Colors are hexadecimal
+    /// strings, and boolean quirks are `0` or `1`. Values that are `None` are left out.
+    ///
+    /// Example:
+    /// ```
+    /// use octopt::Options;
+    ///
+    /// let flat = Options::default().to_flat_map();
+    /// assert_eq!(flat["tickrate"], "500");
+    /// assert_eq!(flat["colors.fillColor"], "#FFFFFF");
+    /// assert_eq!(flat["colors.backgroundColor"], "#000000");
+    /// ```
+    pub fn to_flat_map(&self) -> BTreeMap<String, String> {
+        let to_object = |value: serde_json::Result<serde_json::Value>| match value {
+            Ok(serde_json::Value::Object(object)) => object,
+            _ => serde_json::Map::new(),
+        };
+        let colors = to_object(serde_json::to_value(&self.colors));
+        let quirks = to_object(serde_json::to_value(&self.quirks));
+        let mut flat = BTreeMap::new();
+        for (key, value) in to_object(serde_json::to_value(self)) {
+            let value = match value {
+                serde_json::Value::String(value) => value,
+                serde_json::Value::Bool(value) if quirks.contains_key(&key) => {
+                    u8::from(value).to_string()
+                }
+                value => value.to_string(),
+            };
+            let key = if colors.contains_key(&key) {
+                format!("colors.{}", key)
+            } else if quirks.contains_key(&key) {
+                format!("quirks.{}", key)
+            } else {
+                key
+            };
+            flat.insert(key, value);
+        }
+        flat
+    }
+
+    /// Returns the keys and values of these Options as they're serialized to JSON, in a flat map
+    /// that can be passed to JavaScript as an object. The quirks have Octo's names, like
+    /// `shiftQuirks`.
+    pub fn to_js_object_map(&self) -> BTreeMap<String, serde_json::Value> {
+        match serde_json::to_value(self) {
+            Ok(serde_json::Value::Object(object)) => object.into_iter().collect(),
+            _ => BTreeMap::new(),
+        }
+    }
+
+    /// Serializes these Options to JSON like [`Options::to_string`] does, except that the quirks
+    /// are nested in a `quirks` object with the short names used by newer tools, like `shift`
+    /// instead of Octo's `shiftQuirks`. Octo itself doesn't understand this, but this crate
+    /// accepts both when deserializing.
+    ///
+    /// Example:
+    /// ```
+    /// use octopt::Options;
+    ///
+    /// let json = Options::default().to_json_short_quirk_names();
+    /// assert!(json.contains("\"quirks\":{"));
+    /// assert!(json.contains("\"shift\":false"));
+    /// assert!(!json.contains("shiftQuirks"));
+    /// ```
+    pub fn to_json_short_quirk_names(&self) -> String {
+        let mut json = serde_json::to_value(self).unwrap_or_default();
+        if let Some(object) = json.as_object_mut() {
+            for (octo_name, _) in short_quirks::QUIRK_NAMES {
+                object.remove(octo_name);
+            }
+            let quirks = serde_json::to_value(ShortQuirks::from(self.quirks.clone()));
+            // Leave out the object if no quirks are set, like Octo's names are left out
+            if let Ok(quirks) = quirks {
+                if quirks.as_object().is_some_and(|quirks| !quirks.is_empty()) {
+                    object.insert("quirks".to_string(), quirks);
+                }
+            }
+        }
+        json.to_string()
+    }
+
+    /// Serializes these Options to JSON with only the keys that the given version of Octo
+    /// accepts, so the exported options load in that version. Options that Octo doesn't have at
+    /// all, like `startAddress` and quirks like `overflowIQuirks`, are always left out. The quirks
+    /// have Octo's names, like in [`Options::to_js_object_map`].
+    ///
+    /// Example:
+    /// ```
+    /// use octopt::{OctoVersion, Options};
+    ///
+    /// let json = Options::default().to_json_for_octo_version(OctoVersion::Classic);
+    /// assert!(json.contains("\"shiftQuirks\""));
+    /// assert!(!json.contains("\"logicQuirks\""));
+    /// ```
+    pub fn to_json_for_octo_version(&self, version: OctoVersion) -> String {
+        let keys = version.keys();
+        let object: BTreeMap<String, serde_json::Value> = self
+            .to_js_object_map()
+            .into_iter()
+            .filter(|(key, _)| keys.contains(&key.as_str()))
+            .collect();
+        serde_json::to_string(&object).unwrap_or_default()
+    }
+
+    /// Creates Options from a map in the format returned by [`Options::to_flat_map`]. The
+    /// `colors.` and `quirks.` prefixes are optional, and keys that aren't recognized are
+    /// ignored.
+    ///
+    /// # Errors
+    ///
+    /// Returns an `Err` if a value is invalid for its key.
+    pub fn from_flat_map(flat: &BTreeMap<String, String>) -> Result<Self, ParseError> {
+        let object = flat
+            .iter()
+            .map(|(key, value)| {
+                let key = ["colors.", "quirks."]
+                    .iter()
+                    .find_map(|prefix| key.strip_prefix(prefix))
+                    .unwrap_or(key);
+                (key.to_string(), json_value_from_str(value))
+            })
+            .collect();
+        serde_json::from_value(serde_json::Value::Object(object)).map_err(ParseError::Json)
+    }
+
+    /// Returns a fingerprint of these Options, for telling identical configurations apart from
+    /// different ones. Values that are `None` are treated as their defaults, so two sets of
+    /// Options that are [semantically equal](Self::semantically_eq) have the same fingerprint.
+    ///
+    /// The fingerprint is stable across versions of Rust and platforms, so it can be stored.
+    pub fn fingerprint(&self) -> u64 {
+        fnv1a(&self.canonical_bytes())
+    }
+
+    /// Checks whether these Options are equal to `other` when values that are `None` are treated
+    /// as their defaults.
+    pub fn semantically_eq(&self, other: &Options) -> bool {
+        self.canonical_bytes() == other.canonical_bytes()
+    }
+
+    fn canonical_bytes(&self) -> Vec<u8> {
+        let default = Options::default();
+        let mut bytes = Vec::new();
+        for value in [
+            self.tickrate.or(default.tickrate),
+            self.max_size.or(default.max_size),
+            Some(self.screen_rotation as u16),
+            Some(self.effective_start_address()),
+            Some(self.effective_font_address()),
+        ] {
+            bytes.extend(value.unwrap_or_default().to_le_bytes());
+        }
+        bytes.push(self.effective_plane_count());
+        match self.buzzer_pitch {
+            Some(buzzer_pitch) => {
+                bytes.push(1);
+                bytes.extend(buzzer_pitch.to_le_bytes());
+            }
+            None => bytes.push(0),
+        }
+        for token in [
+            self.font_style.to_string(),
+            self.font_big_style
+                .as_ref()
+                .unwrap_or(&self.font_style)
+                .to_string(),
+            self.touch_input_mode.to_string(),
+        ] {
+            bytes.extend(token.bytes());
+            bytes.push(0);
+        }
+        match &self.custom_font {
+            Some(custom_font) => {
+                let (small, big) = custom_font.get_font_data();
+                let big = big.unwrap_or_default();
+                bytes.push(1);
+                bytes.extend(small);
+                bytes.extend((big.len() as u32).to_le_bytes());
+                bytes.extend(big);
+            }
+            None => bytes.push(0),
+        }
+        self.colors.write_canonical(&mut bytes);
+        self.quirks.write_canonical(&mut bytes);
+        bytes
+    }
+
+    /// Removes all custom colors, so only the options that affect the game's behavior are
+    /// serialized.
+    pub fn without_colors(&mut self) {
+        self.colors = Colors {
+            fill_color: None,
+            fill_color2: None,
+            blend_color: None,
+            background_color: None,
+            buzz_color: None,
+            quiet_color: None,
+            background_transparent: None,
+        };
+    }
+
+    /// Clamps the tickrate to a range that's realistic for the given platform's hardware, so games
+    /// made for a specific target aren't tuned for speeds it can't reach. The ranges are:
+    /// * 1–30 for the COSMAC VIP, DREAM 6800 and ETI-660
+    /// * 1–60 for CHIP-48 and SUPER-CHIP on the HP 48
+    /// * 1–10000 for Octo and XO-CHIP, where 10000 is Octo's "Ludicrous speed"
+    ///
+    /// A tickrate that isn't set is left alone.
+    pub fn clamp_tickrate_for(&mut self, platform: Platform) {
+        let max = match platform {
+            Platform::Vip | Platform::Dream6800 | Platform::Eti660 => 30,
+            Platform::Chip48 | Platform::Schip => 60,
+            Platform::Octo | Platform::XoChip => 10000,
+        };
+        self.tickrate = self.tickrate.map(|tickrate| tickrate.clamp(1, max));
+    }
+
+    /// Returns the number of CHIP-8 instructions executed per second, which some tools use
+    /// instead of the tickrate, since the tickrate is per 60Hz frame.
+    pub fn instructions_per_second(&self) -> Option<u32> {
+        self.tickrate.map(|tickrate| u32::from(tickrate) * 60)
+    }
+
+    /// Returns the number of CHIP-8 instructions executed per millisecond, for interpreters with
+    /// a fixed timestep that isn't tied to 60Hz frames.
+    ///
+    /// Example:
+    /// ```
+    /// use octopt::Options;
+    ///
+    /// let options = Options::default();
+    /// assert_eq!(options.instructions_per_ms(), Some(30.0));
+    /// ```
+    pub fn instructions_per_ms(&self) -> Option<f32> {
+        self.instructions_per_second()
+            .map(|instructions| instructions as f32 / 1000.0)
+    }
+
+    /// Sets the tickrate from a number of CHIP-8 instructions executed per second. The tickrate
+    /// is rounded to the nearest whole number of instructions per frame, with halves rounded up,
+    /// and saturates at [`u16::MAX`].
+    ///
+    /// Example:
+    /// ```
+    /// use octopt::Options;
+    ///
+    /// let mut options = Options::default();
+    /// options.set_tickrate_from_ips(1200);
+    /// assert_eq!(options.tickrate, Some(20));
+    /// ```
+    pub fn set_tickrate_from_ips(&mut self, ips: u32) {
+        let tickrate = (ips / 60 + u32::from(ips % 60 >= 30)).min(u32::from(u16::MAX));
+        self.tickrate = Some(tickrate as u16);
+    }
+
+    /// Returns `true` if these Options are exactly the ones Octo stamps on a brand new project,
+    /// which means they're probably not meaningful metadata about the game, just leftovers from
+    /// whoever made it not changing any settings.
+    pub fn is_octo_new_game_default(&self) -> bool {
+        serde_json::from_str::<Options>(OCTO_NEW_GAME).is_ok_and(|new_game| *self == new_game)
+    }
+
+    /// Normalizes `max_size` values that were emitted by older versions of Octo, but which don't
+    /// correspond to any real platform, to the platform's actual limit. Currently, this is just
+    /// 3215, which Octo used as the default for new games, and which is normalized to the COSMAC
+    /// VIP's 3216. Other values are left alone.
+    pub fn normalize_max_size(&mut self) {
+        if self.max_size == Some(3215) {
+            self.max_size = Some(3216);
+        }
+    }
+
+    /// Normalizes these Options into a canonical form, so that equivalent configurations
+    /// serialize identically. This normalizes `max_size` with [`Options::normalize_max_size`],
+    /// and removes a `font_big_style` that's the same as `font_style`, since that's the default.
+    ///
+    /// Other values are already canonical once they're deserialized: colors are always
+    /// serialized as uppercase hex, quirks as booleans and numbers as integers, regardless of how
+    /// they were written, and JSON keys are serialized in a fixed order.
+    pub fn normalize(&mut self) {
+        self.normalize_max_size();
+        if self.font_big_style.as_ref() == Some(&self.font_style) {
+            self.font_big_style = None;
+        }
+    }
+
+    /// Returns the memory address where the game expects the font, which is `0x50` if
+    /// `font_address` is `None`.
+    pub fn effective_font_address(&self) -> u16 {
+        self.font_address.unwrap_or(0x50)
+    }
+
+    /// Returns the number of bitplanes the game draws to, which is 2 if `plane_count` is `None`.
+    pub fn effective_plane_count(&self) -> u8 {
+        self.plane_count.unwrap_or(2)
+    }
+
+    /// Returns the memory address the game should be loaded at. If `start_address` is `None`,
+    /// this is guessed from the other options: 1536 (`0x600`) if the font is the ETI-660's, since
+    /// that's where the ETI-660 loads games, and 512 (`0x200`) otherwise.
+    pub fn effective_start_address(&self) -> u16 {
+        self.start_address.unwrap_or(match self.font_style {
+            Font::Eti660 => 0x600,
+            _ => 0x200,
+        })
+    }
+
+    /// Returns the font data expected by the game, in the same format as [`Font::get_font_data`].
+    /// If the game has a `custom_font`, that's returned. Otherwise, the small digits are taken
+    /// from `font_style`, while the big digits are taken from `font_big_style` if it's set, or
+    /// `font_style` otherwise.
+    pub fn get_font_data(&self) -> ([u8; 5 * 16], Option<Vec<u8>>) {
+        if let Some(custom_font) = &self.custom_font {
+            return custom_font.get_font_data();
+        }
+        let (small, big) = self.font_style.get_font_data();
+        match &self.font_big_style {
+            Some(font_big_style) => (small, font_big_style.get_font_data().1),
+            None => (small, big),
+        }
+    }
+
+    /// Checks whether the font lacks glyphs that the platform implied by the other options
+    /// needs. Specifically, if the options indicate SUPER-CHIP or XO-CHIP (which have a high
+    /// resolution mode where games can draw big digits), but neither `font_style` nor
+    /// `font_big_style` has big digits, this returns [`Warning::MissingBigFont`].
+    pub fn font_platform_mismatch(&self) -> Option<Warning> {
+        if self.has_high_resolution() && self.get_font_data().1.is_none() {
+            Some(Warning::MissingBigFont)
+        } else {
+            None
+        }
+    }
+
+    /// Returns a list of the features these Options require that an interpreter with the given
+    /// capabilities doesn't support. An empty list means the interpreter should be able to run
+    /// the game as intended.
+    ///
+    /// Example:
+    /// ```
+    /// use octopt::{EmulatorCaps, Options, Platform};
+    ///
+    /// let caps = EmulatorCaps { custom_colors: true, ..EmulatorCaps::default() };
+    /// assert_eq!(Options::new(Platform::Vip).unsupported_by(&caps), Vec::<&str>::new());
+    /// ```
+    pub fn unsupported_by(&self, caps: &EmulatorCaps) -> Vec<&'static str> {
+        let default_colors = Colors::default();
+        let custom_colors = [
+            (self.colors.fill_color, default_colors.fill_color),
+            (self.colors.fill_color2, default_colors.fill_color2),
+            (self.colors.blend_color, default_colors.blend_color),
+            (
+                self.colors.background_color,
+                default_colors.background_color,
+            ),
+            (self.colors.buzz_color, default_colors.buzz_color),
+            (self.colors.quiet_color, default_colors.quiet_color),
+        ]
+        .into_iter()
+        .any(|(color, default)| color.is_some() && color != default);
+
+        [
+            (custom_colors && !caps.custom_colors, "custom colors"),
+            (
+                self.has_high_resolution() && !caps.high_resolution,
+                "high resolution",
+            ),
+            (
+                self.max_size.is_some_and(|max_size| max_size > 3584) && !caps.xo_chip_memory,
+                "XO-CHIP memory",
+            ),
+            (
+                self.touch_input_mode != TouchMode::None && !caps.touch_input,
+                "touch input",
+            ),
+            (
+                self.screen_rotation != ScreenRotation::Normal && !caps.screen_rotation,
+                "screen rotation",
+            ),
+        ]
+        .into_iter()
+        .filter_map(|(unsupported, feature)| unsupported.then_some(feature))
+        .collect()
+    }
+
+    /// Runs every check for likely mistakes in these Options, and returns the problems that were
+    /// found. An empty list means the Options look fine.
+    ///
+    /// Example:
+    /// ```
+    /// use octopt::{Lint, Options};
+    ///
+    /// let mut options = Options::default();
+    /// assert_eq!(options.lint(), Vec::<Lint>::new());
+    /// options.tickrate = Some(0);
+    /// assert_eq!(options.lint(), vec![Lint::ZeroTickrate]);
+    /// ```
+    pub fn lint(&self) -> Vec<Lint> {
+        let quirks = self.quirks.resolve();
+        let clips_vertically = matches!(quirks.clip, ClipMode::ClipBoth | ClipMode::WrapXClipY);
+
+        let default_colors = Colors::default();
+        let unused_colors = self.plane_count == Some(1)
+            && [
+                (self.colors.fill_color2, default_colors.fill_color2),
+                (self.colors.blend_color, default_colors.blend_color),
+            ]
+            .into_iter()
+            .any(|(color, default)| color.is_some() && color != default);
+
+        let start_address = u32::from(self.effective_start_address());
+        let memory_size = if self.max_size.is_some_and(|max_size| max_size > 3584) {
+            0x10000
+        } else {
+            0x1000
+        };
+        let impossible_address = start_address >= memory_size
+            || start_address + u32::from(self.max_size.unwrap_or_default()) > memory_size;
+
+        [
+            (
+                quirks.clip_collision && !clips_vertically,
+                Lint::ClipCollisionWithoutClipping,
+            ),
+            (
+                self.font_platform_mismatch() == Some(Warning::MissingBigFont),
+                Lint::MissingBigFont,
+            ),
+            (unused_colors, Lint::UnusedColors),
+            (impossible_address, Lint::ImpossibleAddress),
+            (self.tickrate == Some(0), Lint::ZeroTickrate),
+        ]
+        .into_iter()
+        .filter_map(|(found, lint)| found.then_some(lint))
+        .collect()
     }
-}
 
-/// Deserializes Options from a JSON string.
-///
-/// This format is used by Octo in Octocarts and HTML exports, as well as the Chip-8 Archive.
-impl FromStr for Options {
-    type Err = serde_json::Error;
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        serde_json::from_str(s)
+    /// Returns the width and height of the display in pixels, as the player sees it, ie. with the
+    /// screen rotation applied. This is 128x64 if the options indicate a platform with a high
+    /// resolution mode (SUPER-CHIP or XO-CHIP), and 64x32 otherwise.
+    pub fn display_dimensions(&self) -> (u16, u16) {
+        let (width, height) = if self.has_high_resolution() {
+            (128, 64)
+        } else {
+            (64, 32)
+        };
+        match self.screen_rotation {
+            ScreenRotation::Normal | ScreenRotation::UpsideDown => (width, height),
+            ScreenRotation::ClockWise | ScreenRotation::CounterClockWise => (height, width),
+        }
     }
-}
 
-impl Options {
-    /// Deserializes Options from an INI string.
+    /// Guesses whether the game needs an interpreter with SUPER-CHIP's high resolution (128x64)
+    /// mode. This is the case if the options indicate a platform with a high resolution mode,
+    /// like with quirks that only make sense in high resolution (such as `hires_collision` and
+    /// `scroll`) or XO-CHIP's memory size, or if the game explicitly asks for a font with big
+    /// digits, which are only used in high resolution, through `font_big_style` or
+    /// `custom_font`.
     ///
-    /// # Errors
+    /// Example:
+    /// ```
+    /// use octopt::{Options, Platform};
     ///
-    /// Returns an `Err` if deserialization from the INI failed.
-    pub fn from_ini(s: &str) -> Result<Self, serde_ini::de::Error> {
-        Ok(Self::from(OptionsIni::from_str(s)?))
+    /// assert!(Options::new(Platform::Schip).requires_high_resolution());
+    /// assert!(!Options::new(Platform::Vip).requires_high_resolution());
+    /// ```
+    pub fn requires_high_resolution(&self) -> bool {
+        self.has_high_resolution()
+            || self
+                .font_big_style
+                .as_ref()
+                .is_some_and(|font| font.get_font_data().1.is_some())
+            || self
+                .custom_font
+                .as_ref()
+                .is_some_and(|font| font.big.is_some())
     }
 
-    /// Serializes Options to an INI string.
-    pub fn to_ini(self) -> String {
-        OptionsIni::to_string(&OptionsIni::from(self))
+    // Guesses whether the target platform has a high resolution mode, based on the quirks that
+    // only make sense in high resolution, and the memory size of XO-CHIP.
+    fn has_high_resolution(&self) -> bool {
+        self.max_size.is_some_and(|max_size| max_size > 3584)
+            || self.quirks.hires_collision == Some(true)
+            || self.quirks.clip_collision == Some(true)
+            || self.quirks.scroll == Some(true)
     }
 
     /// Get a preset set of Options based on a target Platform.
@@ -494,15 +2906,20 @@ impl Options {
                 max_size: Some(3216),
                 screen_rotation: ScreenRotation::Normal,
                 font_style: Font::Vip,
+                font_big_style: None,
+                plane_count: None,
+                buzzer_pitch: None,
+                font_address: None,
+                custom_font: None,
                 touch_input_mode: TouchMode::None,
                 start_address: Some(0x200),
                 colors: Colors::default(),
                 quirks: Quirks {
                     shift: Some(false),
-                    load_store: Some(false),
+                    load_store: Some(LoadStoreMode::IncrementByXPlus1),
                     jump0: Some(false),
                     logic: Some(true),
-                    clip: Some(true),
+                    clip: Some(ClipMode::ClipBoth),
                     vblank: Some(true),
                     vf_order: Some(true),
                     delay_wrap: Some(false),
@@ -520,15 +2937,20 @@ impl Options {
                 max_size: Some(3216), // TODO check this
                 screen_rotation: ScreenRotation::Normal,
                 font_style: Font::Dream6800,
+                font_big_style: None,
+                plane_count: None,
+                buzzer_pitch: None,
+                font_address: None,
+                custom_font: None,
                 touch_input_mode: TouchMode::None,
                 start_address: Some(0x200),
                 colors: Colors::default(),
                 quirks: Quirks {
                     shift: Some(false),
-                    load_store: Some(false),
+                    load_store: Some(LoadStoreMode::IncrementByXPlus1),
                     jump0: Some(false),
                     logic: Some(true),
-                    clip: Some(true),
+                    clip: Some(ClipMode::ClipBoth),
                     vblank: Some(true),
                     vf_order: Some(true),
                     delay_wrap: Some(true),
@@ -543,25 +2965,31 @@ impl Options {
             },
             Platform::Eti660 => Self {
                 tickrate: Some(20),
-                max_size: Some(3216), // TODO check this
+                // The rest of its 4K memory after the start address. TODO check this
+                max_size: Some(0x1000 - 0x600),
                 screen_rotation: ScreenRotation::Normal,
                 font_style: Font::Eti660,
+                font_big_style: None,
+                plane_count: None,
+                buzzer_pitch: None,
+                font_address: None,
+                custom_font: None,
                 touch_input_mode: TouchMode::None,
                 start_address: Some(0x600),
                 colors: Colors::default(),
                 quirks: Quirks {
                     // TODO check these
                     shift: Some(false),
-                    load_store: Some(false),
+                    load_store: Some(LoadStoreMode::IncrementByXPlus1),
                     jump0: Some(false),
                     logic: Some(true),
-                    clip: Some(true),
+                    clip: Some(ClipMode::ClipBoth),
                     vblank: Some(true),
                     vf_order: Some(true),
                     delay_wrap: Some(false),
                     overflow_i: Some(false),
                     lores_dxy0: Some(LoResDxy0Behavior::NoOp),
-                    // The following are all None, as CHIP-8 on the VIP doesn't support high resolution:
+                    // The following are all None, as CHIP-8 on the ETI-660 doesn't support high resolution:
                     hires_collision: None,
                     clip_collision: None,
                     scroll: None,
@@ -573,16 +3001,21 @@ impl Options {
                 max_size: Some(3583), // TODO check this
                 screen_rotation: ScreenRotation::Normal,
                 font_style: Font::Schip, // TODO check this
+                font_big_style: None,
+                plane_count: None,
+                buzzer_pitch: None,
+                font_address: None,
+                custom_font: None,
                 touch_input_mode: TouchMode::None,
                 start_address: Some(0x200),
                 colors: Colors::default(), // TODO LCD
                 quirks: Quirks {
                     // TODO check these
                     shift: Some(true),
-                    load_store: Some(true),
+                    load_store: Some(LoadStoreMode::Unchanged),
                     jump0: Some(true),
                     logic: Some(false),
-                    clip: Some(true),
+                    clip: Some(ClipMode::ClipBoth),
                     vblank: Some(false),
                     vf_order: None,
                     delay_wrap: Some(false),
@@ -599,15 +3032,20 @@ impl Options {
                 max_size: Some(3583),
                 screen_rotation: ScreenRotation::Normal,
                 font_style: Font::Schip,
+                font_big_style: None,
+                plane_count: None,
+                buzzer_pitch: None,
+                font_address: None,
+                custom_font: None,
                 touch_input_mode: TouchMode::None,
                 start_address: Some(0x200),
                 colors: Colors::default(), // TODO LCD
                 quirks: Quirks {
                     shift: Some(true),
-                    load_store: Some(true),
+                    load_store: Some(LoadStoreMode::Unchanged),
                     jump0: Some(true),
                     logic: Some(false),
-                    clip: Some(true),
+                    clip: Some(ClipMode::ClipBoth),
                     vblank: Some(false),
                     vf_order: None, // TODO check this
                     res_clear: Some(false),
@@ -623,6 +3061,20 @@ impl Options {
     }
 }
 
+/// Returns the preset Options for a Platform; the same as [`Options::new`].
+impl From<Platform> for Options {
+    fn from(platform: Platform) -> Self {
+        Self::new(platform)
+    }
+}
+
+/// Returns the preset Quirks for a Platform; the same as the quirks in [`Options::new`].
+impl From<Platform> for Quirks {
+    fn from(platform: Platform) -> Self {
+        Options::new(platform).quirks
+    }
+}
+
 /// Serializes Options into a JSON string.
 ///
 /// This format is used by Octo in Octocarts and HTML exports, as well as the Chip-8 Archive.
@@ -635,6 +3087,412 @@ impl fmt::Display for Options {
     }
 }
 
+/// Where an interpreter puts its font data in memory, which allows the small and big digits to
+/// come from different fonts.
+///
+/// Example:
+/// ```
+/// use octopt::{Font, FontLayout};
+///
+/// let layout = FontLayout {
+///     small: Font::Vip,
+///     big: Some(Font::Schip),
+///     small_addr: 0x50,
+///     big_addr: 0xA0,
+/// };
+/// let image = layout.memory_image().unwrap();
+/// assert_eq!(image[0], (0x50, 0xF0));
+/// assert_eq!(image[80], (0xA0, 0x3C));
+/// ```
+#[derive(Debug, PartialEq, Clone)]
+pub struct FontLayout {
+    /// The font to take the small digits from
+    pub small: Font,
+    /// The font to take the big digits from, if any
+    pub big: Option<Font>,
+    /// The memory address of the first small digit
+    pub small_addr: u16,
+    /// The memory address of the first big digit, which is ignored if `big` is `None`
+    pub big_addr: u16,
+}
+
+impl FontLayout {
+    /// Returns the font data as pairs of memory addresses and bytes, like
+    /// [`Font::get_font_blob`], so they can be spliced into a memory image.
+    ///
+    /// # Errors
+    ///
+    /// Returns an `Err` if the small and big digits overlap, if they don't fit in the first 512
+    /// bytes of memory, or if the big font doesn't have big digits.
+    pub fn memory_image(&self) -> Result<Vec<(u16, u8)>, LayoutError> {
+        let (small, _) = self.small.get_font_data();
+        let big = match &self.big {
+            Some(font) => font
+                .get_font_data()
+                .1
+                .ok_or(LayoutError::MissingBigDigits)?,
+            None => Vec::new(),
+        };
+        let small_end = usize::from(self.small_addr) + small.len();
+        let big_end = usize::from(self.big_addr) + big.len();
+        if small_end > 0x200 || (!big.is_empty() && big_end > 0x200) {
+            return Err(LayoutError::OutOfBounds);
+        }
+        if !big.is_empty()
+            && usize::from(self.small_addr) < big_end
+            && usize::from(self.big_addr) < small_end
+        {
+            return Err(LayoutError::Overlap);
+        }
+        Ok((self.small_addr..)
+            .zip(small)
+            .chain((self.big_addr..).zip(big))
+            .collect())
+    }
+}
+
+/// Descriptive metadata that commonly accompanies [Options], like in the entries of the CHIP-8
+/// Community Archive's `programs.json`.
+///
+/// All fields are optional, since most sources only include some of them (or none at all).
+#[skip_serializing_none]
+#[derive(Debug, Default, PartialEq, Serialize, Deserialize)]
+#[non_exhaustive]
+pub struct CartMetadata {
+    /// The name of the game. Called `title` in the CHIP-8 Community Archive.
+    #[serde(alias = "title")]
+    pub name: Option<String>,
+    /// A description of the game. Called `desc` in the CHIP-8 Community Archive.
+    #[serde(alias = "desc")]
+    pub description: Option<String>,
+    /// The game's author. The CHIP-8 Community Archive has a list of `authors`, which are joined
+    /// together with commas.
+    #[serde(
+        alias = "authors",
+        default,
+        deserialize_with = "some_string_from_str_or_list"
+    )]
+    pub author: Option<String>,
+    /// The options the game requires.
+    #[serde(default)]
+    pub options: Options,
+    /// The platform the game was made for. Platforms that aren't known are ignored.
+    #[serde(default, deserialize_with = "some_platform_lenient")]
+    pub platform: Option<Platform>,
+}
+
+impl CartMetadata {
+    /// Decides every quirk the game requires. Quirks that aren't set in the game's options get
+    /// the value they have on the game's `platform`, if it has one, and otherwise the value they
+    /// have in [`Quirks::default`].
+    pub fn resolve_quirks(&self) -> ResolvedQuirks {
+        match self.platform {
+            Some(platform) => self.options.quirks.resolve_for(platform),
+            None => self.options.quirks.resolve(),
+        }
+    }
+}
+
+/// Deserializes CartMetadata from a JSON string, such as a single entry in the CHIP-8 Community
+/// Archive's `programs.json`.
+impl FromStr for CartMetadata {
+    type Err = ParseError;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        serde_json::from_str(s).map_err(ParseError::Json)
+    }
+}
+
+/// An Octo project, ie. a game's source code saved together with its [Options], like in the JSON
+/// that Octo uses for sharing and saving projects.
+#[derive(Debug, Default, PartialEq, Serialize, Deserialize)]
+#[non_exhaustive]
+pub struct Project {
+    /// The options the game requires.
+    #[serde(default)]
+    pub options: Options,
+    /// The game's Octo source code. Called `program` by Octo.
+    #[serde(rename = "program", alias = "source")]
+    pub source: String,
+    /// The project's name, which is empty if it doesn't have one.
+    #[serde(default)]
+    pub label: String,
+}
+
+impl Project {
+    /// Deserializes a Project from Octo's project JSON, like
+    /// `{"program":": main ...","options":{...}}`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an `Err` if the JSON is invalid, or if it doesn't contain a program.
+    pub fn from_octo_json(json: &str) -> Result<Self, ParseError> {
+        serde_json::from_str(json).map_err(ParseError::Json)
+    }
+}
+
+/// The features a CHIP-8 interpreter supports, for checking whether it can run a game with
+/// [`Options::unsupported_by`]. The default is an interpreter that supports none of them.
+#[derive(Debug, Default, PartialEq, Clone, Copy)]
+pub struct EmulatorCaps {
+    /// The interpreter can display custom colors.
+    pub custom_colors: bool,
+    /// The interpreter supports SUPER-CHIP's high resolution (128x64) mode.
+    pub high_resolution: bool,
+    /// The interpreter has XO-CHIP's 64K of memory.
+    pub xo_chip_memory: bool,
+    /// The interpreter supports Octo's touch input modes.
+    pub touch_input: bool,
+    /// The interpreter can rotate the display.
+    pub screen_rotation: bool,
+}
+
+/// A potential problem with a set of [Options], which doesn't prevent them from being used but
+/// is likely to be a mistake.
+#[derive(Debug, PartialEq, Clone, Copy)]
+#[non_exhaustive]
+pub enum Warning {
+    /// The font has no big digits, but the options indicate a platform with a high resolution
+    /// mode (SUPER-CHIP or XO-CHIP), where games can use them.
+    MissingBigFont,
+}
+
+impl fmt::Display for Warning {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Warning::MissingBigFont => write!(
+                f,
+                "the font has no big digits, but the platform has a high resolution mode"
+            ),
+        }
+    }
+}
+
+/// A problem with a set of [Options], as found by [`Options::lint`].
+#[derive(Debug, PartialEq, Clone, Copy)]
+#[non_exhaustive]
+pub enum Lint {
+    /// [`Quirks::clip_collision`] is enabled, but sprites wrap around the bottom of the screen
+    /// instead of being clipped, so they can never run off it.
+    ClipCollisionWithoutClipping,
+    /// The same as [`Warning::MissingBigFont`].
+    MissingBigFont,
+    /// The game only draws to one bitplane, but sets colors that are only used with two.
+    UnusedColors,
+    /// The start address is outside of the memory, or the game can't fit in the memory after it.
+    ImpossibleAddress,
+    /// The tickrate is 0, so the game will never run.
+    ZeroTickrate,
+}
+
+impl Lint {
+    /// Returns how serious the problem is.
+    pub fn severity(&self) -> Severity {
+        match self {
+            Lint::UnusedColors => Severity::Info,
+            Lint::ClipCollisionWithoutClipping | Lint::MissingBigFont => Severity::Warning,
+            Lint::ImpossibleAddress | Lint::ZeroTickrate => Severity::Error,
+        }
+    }
+}
+
+impl fmt::Display for Lint {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Lint::ClipCollisionWithoutClipping => write!(
+                f,
+                "clip collision is enabled, but sprites wrap instead of being clipped"
+            ),
+            Lint::MissingBigFont => Warning::MissingBigFont.fmt(f),
+            Lint::UnusedColors => write!(
+                f,
+                "colors for the second bitplane are set, but only one bitplane is used"
+            ),
+            Lint::ImpossibleAddress => write!(f, "the game doesn't fit in memory"),
+            Lint::ZeroTickrate => write!(f, "the tickrate is 0"),
+        }
+    }
+}
+
+/// How serious a [Lint] is, from least to most serious.
+#[derive(Display, Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Copy)]
+#[display(style = "lowercase")]
+pub enum Severity {
+    /// Probably harmless, but worth knowing about.
+    Info,
+    /// Likely a mistake, but the game might still work.
+    Warning,
+    /// The game can't work as configured.
+    Error,
+}
+
+/// An error that occurred while deserializing CHIP-8 configuration settings.
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum ParseError {
+    /// The JSON was invalid, or didn't have the expected structure.
+    Json(serde_json::Error),
+    /// The input couldn't be read.
+    Io(std::io::Error),
+    /// The input was larger than the given limit, in bytes.
+    TooLarge(usize),
+    /// The INI was invalid, or didn't have the expected structure.
+    Ini(serde_ini::de::Error),
+    /// The input isn't in a format that can be read.
+    UnsupportedFormat,
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseError::Json(e) => write!(f, "invalid JSON: {}", e),
+            ParseError::Io(e) => write!(f, "couldn't read input: {}", e),
+            ParseError::TooLarge(limit) => write!(f, "input is larger than {} bytes", limit),
+            ParseError::Ini(e) => write!(f, "invalid INI: {}", e),
+            ParseError::UnsupportedFormat => write!(f, "unsupported format"),
+        }
+    }
+}
+
+impl std::error::Error for ParseError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            ParseError::Json(e) => Some(e),
+            ParseError::Io(e) => Some(e),
+            ParseError::Ini(e) => Some(e),
+            ParseError::TooLarge(_) | ParseError::UnsupportedFormat => None,
+        }
+    }
+}
+
+/// An error that occurred while setting a single option from a string.
+#[derive(Debug, PartialEq, Clone)]
+#[non_exhaustive]
+pub enum SetError {
+    /// The string wasn't of the form `key=value`.
+    MissingEquals,
+    /// The key isn't a known option.
+    UnknownKey(String),
+    /// The value isn't valid for the key.
+    InvalidValue(String),
+}
+
+impl fmt::Display for SetError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SetError::MissingEquals => write!(f, "expected a string of the form key=value"),
+            SetError::UnknownKey(key) => write!(f, "unknown key: {}", key),
+            SetError::InvalidValue(value) => write!(f, "invalid value: {}", value),
+        }
+    }
+}
+
+impl std::error::Error for SetError {}
+
+/// An error in a [FontLayout], which makes it impossible to load into memory.
+#[derive(Debug, PartialEq, Clone, Copy)]
+#[non_exhaustive]
+pub enum LayoutError {
+    /// The small and big digits would overlap in memory.
+    Overlap,
+    /// The digits wouldn't fit in the 512 bytes of memory reserved for the interpreter.
+    OutOfBounds,
+    /// The font chosen for the big digits doesn't have any.
+    MissingBigDigits,
+}
+
+impl fmt::Display for LayoutError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LayoutError::Overlap => write!(f, "the small and big digits overlap"),
+            LayoutError::OutOfBounds => write!(f, "the digits don't fit before address 0x200"),
+            LayoutError::MissingBigDigits => write!(f, "the big font has no big digits"),
+        }
+    }
+}
+
+impl std::error::Error for LayoutError {}
+
+// Converts the SCREAMING_SNAKE_CASE name of an environment variable to a camelCase JSON key.
+fn camel_case_from_env(name: &str) -> String {
+    let mut key = String::with_capacity(name.len());
+    let mut upper = false;
+    for c in name.chars() {
+        if c == '_' {
+            upper = true;
+        } else if upper {
+            key.push(c.to_ascii_uppercase());
+            upper = false;
+        } else {
+            key.push(c.to_ascii_lowercase());
+        }
+    }
+    key
+}
+
+// Deserializes an optional field from `json`, which was read from `value`, with the field's
+// deserializer. `null` unsets the field, but any other value that the deserializer leniently
+// turns into `None` is invalid.
+fn parse_optional<T>(
+    value: &str,
+    json: serde_json::Value,
+    deserialize: impl FnOnce(serde_json::Value) -> serde_json::Result<Option<T>>,
+) -> Result<Option<T>, SetError> {
+    if json.is_null() {
+        return Ok(None);
+    }
+    match deserialize(json) {
+        Ok(Some(parsed)) => Ok(Some(parsed)),
+        Ok(None) | Err(_) => Err(SetError::InvalidValue(value.to_string())),
+    }
+}
+
+// Interprets a value given as plain text, like in an .8o header or a debug console, as the JSON
+// value it would be written as in an Octo configuration.
+fn json_value_from_str(value: &str) -> serde_json::Value {
+    match value.trim() {
+        "null" => serde_json::Value::Null,
+        "true" => serde_json::Value::Bool(true),
+        "false" => serde_json::Value::Bool(false),
+        value => match value.parse::<u64>() {
+            Ok(number) => serde_json::Value::from(number),
+            Err(_) => serde_json::Value::from(value),
+        },
+    }
+}
+
+fn some_string_from_str_or_list<'de, D>(deserializer: D) -> Result<Option<String>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum StrOrList {
+        Str(String),
+        List(Vec<String>),
+    }
+
+    Ok(match Option::<StrOrList>::deserialize(deserializer)? {
+        Some(StrOrList::Str(v)) => Some(v),
+        Some(StrOrList::List(v)) if !v.is_empty() => Some(v.join(", ")),
+        _ => None,
+    })
+}
+
+fn some_platform_lenient<'de, D>(deserializer: D) -> Result<Option<Platform>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    Ok(Option::<serde_json::Value>::deserialize(deserializer)?
+        .and_then(|platform| Platform::deserialize(platform).ok()))
+}
+
+// The 64-bit FNV-1a hash, which we use instead of `std::hash` since its output is stable.
+fn fnv1a(bytes: &[u8]) -> u64 {
+    bytes.iter().fold(0xcbf2_9ce4_8422_2325, |hash, byte| {
+        (hash ^ u64::from(*byte)).wrapping_mul(0x0100_0000_01b3)
+    })
+}
+
 // Could have used serde_aux::field_attributes::deserialize_option_number_from_string here
 // but let's not pull in that dep just for this. If it had deserialize_option_bool_from_anything
 // then we'd be talking.
@@ -644,39 +3502,186 @@ where
 {
     #[derive(Deserialize)]
     #[serde(untagged)]
-    enum U16OrStr<'a> {
+    enum U16OrStr {
         U16(u16),
-        Str(&'a str),
+        F64(f64),
+        Str(String),
     }
 
     Ok(match U16OrStr::deserialize(deserializer)? {
-        U16OrStr::Str(v) => match v.parse() {
-            Ok(v) => Some(v),
-            Err(_) => None,
+        U16OrStr::Str(v) => match v.strip_prefix("0x").or_else(|| v.strip_prefix("0X")) {
+            Some(hex) => u16::from_str_radix(hex, 16).ok(),
+            None => v.parse().ok(),
         },
         U16OrStr::U16(v) => Some(v),
+        // Some exports write numbers as floats, like 20.0
+        U16OrStr::F64(v) if (0.0..=f64::from(u16::MAX)).contains(&v.round()) => {
+            Some(v.round() as u16)
+        }
+        U16OrStr::F64(v) => {
+            return Err(de::Error::invalid_value(
+                Unexpected::Float(v),
+                &"a number between 0 and 65535",
+            ))
+        }
     })
 }
 
-fn some_bool_from_int<'de, D>(deserializer: D) -> Result<Option<bool>, D::Error>
+fn flat_or_nested_quirks<'de, D>(deserializer: D) -> Result<Quirks, D::Error>
 where
     D: Deserializer<'de>,
 {
     #[derive(Deserialize)]
-    #[serde(untagged)]
-    enum BoolOrU8 {
-        Bool(bool),
-        U8(u8),
+    struct FlatOrNested {
+        quirks: Option<ShortQuirks>,
+        #[serde(flatten)]
+        flat: Quirks,
+    }
+
+    let FlatOrNested { quirks, flat } = FlatOrNested::deserialize(deserializer)?;
+    Ok(match quirks {
+        Some(nested) => Quirks::from(nested).or(flat),
+        None => flat,
+    })
+}
+
+fn flat_or_nested_colors<'de, D>(deserializer: D) -> Result<Colors, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    // The colors as newer tools nest them, without the redundant "Color" suffix
+    #[derive(Deserialize)]
+    #[serde(rename_all = "camelCase")]
+    struct NestedColors {
+        fill: Option<Color>,
+        fill2: Option<Color>,
+        blend: Option<Color>,
+        background: Option<Color>,
+        #[serde(alias = "buzzer")]
+        buzz: Option<Color>,
+        #[serde(alias = "silence")]
+        quiet: Option<Color>,
+        background_transparent: Option<bool>,
+    }
+
+    #[derive(Deserialize)]
+    struct FlatOrNested {
+        colors: Option<NestedColors>,
+        #[serde(flatten)]
+        flat: Colors,
     }
 
-    match BoolOrU8::deserialize(deserializer)? {
-        BoolOrU8::Bool(v) => Ok(Some(v)),
-        BoolOrU8::U8(1) => Ok(Some(true)),
-        BoolOrU8::U8(0) => Ok(Some(false)),
-        BoolOrU8::U8(other) => Err(de::Error::invalid_value(
+    let FlatOrNested { colors, flat } = FlatOrNested::deserialize(deserializer)?;
+    Ok(match colors {
+        Some(nested) => Colors {
+            fill_color: nested.fill.or(flat.fill_color),
+            fill_color2: nested.fill2.or(flat.fill_color2),
+            blend_color: nested.blend.or(flat.blend_color),
+            background_color: nested.background.or(flat.background_color),
+            buzz_color: nested.buzz.or(flat.buzz_color),
+            quiet_color: nested.quiet.or(flat.quiet_color),
+            background_transparent: nested
+                .background_transparent
+                .or(flat.background_transparent),
+        },
+        None => flat,
+    })
+}
+
+fn some_plane_count<'de, D>(deserializer: D) -> Result<Option<u8>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    match Option::<u8>::deserialize(deserializer)? {
+        Some(count @ 1..=4) => Ok(Some(count)),
+        Some(other) => Err(de::Error::invalid_value(
             Unexpected::Unsigned(u64::from(other)),
-            &"zero or one",
+            &"a plane count between 1 and 4",
         )),
+        None => Ok(None),
+    }
+}
+
+fn some_bool_from_int<'de, D>(deserializer: D) -> Result<Option<bool>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    // Some emitters write null for quirks they don't know, which means the same as leaving it out
+    Option::<BoolOrU8OrStr>::deserialize(deserializer)?
+        .map(BoolOrU8OrStr::into_mode)
+        .transpose()
+}
+
+// A quirk that's a boolean in Octo, but that may have more modes than the two that `false` and
+// `true` stand for, like ClipMode. The other modes are written as their names.
+pub(crate) trait BoolOrMode:
+    Copy + PartialEq + std::str::FromStr + std::fmt::Display
+{
+    // The modes that `false` and `true` stand for
+    const FALSE: Self;
+    const TRUE: Self;
+    // What a value that isn't a boolean or a mode name is reported as not being
+    const EXPECTING: &'static str;
+
+    fn to_bool(self) -> Option<bool> {
+        if self == Self::FALSE {
+            Some(false)
+        } else if self == Self::TRUE {
+            Some(true)
+        } else {
+            None
+        }
+    }
+}
+
+impl BoolOrMode for bool {
+    const FALSE: Self = false;
+    const TRUE: Self = true;
+    const EXPECTING: &'static str = "zero, one, on or off";
+}
+
+// How a BoolOrMode is written in the wild: as a boolean, as 0 or 1, or as a string, which can be
+// any of the ways `bool_from_str` accepts as well as a mode name.
+#[derive(Deserialize)]
+#[serde(untagged)]
+pub(crate) enum BoolOrU8OrStr {
+    Bool(bool),
+    U8(u8),
+    Str(String),
+}
+
+impl BoolOrU8OrStr {
+    pub(crate) fn into_mode<M: BoolOrMode, E: de::Error>(self) -> Result<M, E> {
+        let value = match self {
+            BoolOrU8OrStr::Bool(value) => value,
+            BoolOrU8OrStr::U8(0) => false,
+            BoolOrU8OrStr::U8(1) => true,
+            BoolOrU8OrStr::U8(other) => {
+                return Err(E::invalid_value(
+                    Unexpected::Unsigned(u64::from(other)),
+                    &M::EXPECTING,
+                ))
+            }
+            BoolOrU8OrStr::Str(name) => match bool_from_str(&name) {
+                Some(value) => value,
+                None => {
+                    return name
+                        .parse()
+                        .map_err(|_| E::invalid_value(Unexpected::Str(&name), &M::EXPECTING))
+                }
+            },
+        };
+        Ok(if value { M::TRUE } else { M::FALSE })
+    }
+}
+
+// Parses the ways booleans are written in the wild: 0 and 1, true and false, and on and off, which
+// some YAML and INI files use. Case doesn't matter.
+pub(crate) fn bool_from_str(value: &str) -> Option<bool> {
+    match value.trim().to_ascii_lowercase().as_str() {
+        "1" | "true" | "on" => Some(true),
+        "0" | "false" | "off" => Some(false),
+        _ => None,
     }
 }
 
@@ -685,7 +3690,7 @@ where
 /// It's not likely that many (or any) historical CHIP-8 games depend on a particular font, but it's
 /// possible, and for that reason (and to make historical games look accurate) the font can be
 /// overriden here _and_ you can get the sprite data for the fonts by calling [`get_font_data`].
-#[derive(Display, FromStr, Debug, PartialEq, Serialize, Deserialize)]
+#[derive(Display, FromStr, Debug, PartialEq, Serialize, Deserialize, Clone)]
 // TODO: Should this actually be snakecase? https://github.com/JohnEarnest/c-octo#configuration-file
 #[non_exhaustive]
 pub enum Font {
@@ -725,6 +3730,48 @@ pub enum Font {
     #[serde(rename = "akouz1")]
     #[display("akouz1")]
     AKouZ1,
+    /// No built-in font at all, like some interpreters that required games to supply their own
+    /// digits. The small digits are all blank, and there are no big digits.
+    #[serde(rename = "none")]
+    #[display("none")]
+    None,
+}
+
+/// Font data that a game supplies itself, instead of using one of the built-in [Font]s. In JSON,
+/// the sprite data is written as Base64 strings.
+///
+/// Example:
+/// ```
+/// use octopt::CustomFont;
+///
+/// let font: CustomFont = serde_json::from_str(r#"{"small":"8JCQkPA="}"#).unwrap();
+/// assert_eq!(font.small, vec![0xF0, 0x90, 0x90, 0x90, 0xF0]);
+/// ```
+#[serde_as]
+#[skip_serializing_none]
+#[derive(Debug, Default, PartialEq, Serialize, Deserialize, Clone)]
+pub struct CustomFont {
+    /// Sprites that are 5 bytes tall, one for each hexadecimal digit starting at 0. Fonts with
+    /// fewer than 16 digits are padded with blank sprites by [`CustomFont::get_font_data`].
+    #[serde_as(as = "Base64")]
+    pub small: Vec<u8>,
+    /// Sprites that are 10 bytes tall, one for each digit starting at 0, if the font has big
+    /// digits.
+    #[serde_as(as = "Option<Base64>")]
+    #[serde(default)]
+    pub big: Option<Vec<u8>>,
+}
+
+impl CustomFont {
+    /// Returns the font data in the same format as [`Font::get_font_data`]. The small digits are
+    /// padded with zeroes, or truncated, to 16 sprites.
+    pub fn get_font_data(&self) -> ([u8; 5 * 16], Option<Vec<u8>>) {
+        let mut small = [0; 5 * 16];
+        for (byte, custom) in small.iter_mut().zip(&self.small) {
+            *byte = *custom;
+        }
+        (small, self.big.clone())
+    }
 }
 
 /// The default font is Octo's font, as it's the modern standard and contains all hexadecimal digits
@@ -962,6 +4009,170 @@ impl Font {
                     0xFF, 0xC0, 0xC0, 0xC0, 0xFE, 0xC0, 0xC0, 0xC0, 0xC0, 0xC0, // F
                 ]),
             ),
+            Font::None => ([0; 5 * 16], None),
+        }
+    }
+
+    /// Returns the font data from [`Font::get_font_data`] as pairs of memory addresses and bytes,
+    /// with the small digits starting at `load_small_at` and the big digits (if the font has any)
+    /// starting at `load_big_at`, so they can be spliced into a memory image.
+    ///
+    /// Example:
+    /// ```
+    /// use octopt::Font;
+    ///
+    /// let blob = Font::Octo.get_font_blob(0x50, Some(0xA0));
+    /// assert_eq!(blob[0], (0x50, 0xF0));
+    /// assert_eq!(blob[80], (0xA0, 0xFF));
+    /// ```
+    pub fn get_font_blob(&self, load_small_at: u16, load_big_at: Option<u16>) -> Vec<(u16, u8)> {
+        let (small, big) = self.get_font_data();
+        let mut blob: Vec<(u16, u8)> = (load_small_at..).zip(small).collect();
+        if let (Some(load_big_at), Some(big)) = (load_big_at, big) {
+            blob.extend((load_big_at..).zip(big));
+        }
+        blob
+    }
+
+    /// Renders the glyph for the given hexadecimal digit as lines of `#` (set pixels) and spaces
+    /// (unset pixels), for eyeballing fonts in a terminal. The glyph is as wide as its widest row.
+    ///
+    /// Returns `None` if the font doesn't have the glyph, for example big digits in a font that
+    /// only has small ones, or digits above `0xF`.
+    ///
+    /// Example:
+    /// ```
+    /// use octopt::Font;
+    ///
+    /// assert_eq!(
+    ///     Font::Octo.render_glyph_ascii(0xC, false),
+    ///     Some("####\n#   \n#   \n#   \n####".to_string())
+    /// );
+    /// ```
+    pub fn render_glyph_ascii(&self, digit: u8, big: bool) -> Option<String> {
+        let (small, big_data) = self.get_font_data();
+        let digit = usize::from(digit);
+        let rows = if big {
+            big_data?.get(digit * 10..digit * 10 + 10)?.to_vec()
+        } else {
+            small.get(digit * 5..digit * 5 + 5)?.to_vec()
+        };
+        let width = rows
+            .iter()
+            .map(|row| 8 - row.trailing_zeros().min(8) as usize)
+            .max()
+            .unwrap_or(0);
+        let lines: Vec<String> = rows
+            .iter()
+            .map(|row| {
+                (0..width)
+                    .map(|bit| if row & (0x80 >> bit) != 0 { '#' } else { ' ' })
+                    .collect()
+            })
+            .collect();
+        Some(lines.join("\n"))
+    }
+
+    /// Compares the small digits of two fonts, and returns the hexadecimal digits whose sprites
+    /// differ, in ascending order. An empty list means the fonts have identical small digits.
+    ///
+    /// Example:
+    /// ```
+    /// use octopt::Font;
+    ///
+    /// assert_eq!(Font::glyph_diff(Font::Octo, Font::Octo), Vec::<u8>::new());
+    /// ```
+    pub fn glyph_diff(a: Font, b: Font) -> Vec<u8> {
+        let (a, _) = a.get_font_data();
+        let (b, _) = b.get_font_data();
+        (0..16)
+            .zip(a.chunks(5).zip(b.chunks(5)))
+            .filter_map(|(digit, (a, b))| (a != b).then_some(digit))
+            .collect()
+    }
+
+    /// Compares two fonts, both their small and big digits, for documenting how fonts differ.
+    /// `differing_small` is the same as [`Font::glyph_diff`].
+    ///
+    /// Example:
+    /// ```
+    /// use octopt::Font;
+    ///
+    /// let comparison = Font::compare(Font::Octo, Font::Schip);
+    /// assert!(comparison.small_identical);
+    /// assert_eq!(comparison.big_identical, Some(false));
+    /// ```
+    pub fn compare(a: Font, b: Font) -> FontComparison {
+        let big_identical = match (a.get_font_data().1, b.get_font_data().1) {
+            (Some(a), Some(b)) => Some(a == b),
+            _ => None,
+        };
+        let differing_small = Font::glyph_diff(a, b);
+        FontComparison {
+            small_identical: differing_small.is_empty(),
+            big_identical,
+            differing_small,
+        }
+    }
+
+    /// Returns the font data from [`Font::get_font_data`] as a [FontData], which also describes
+    /// the big digits.
+    ///
+    /// Example:
+    /// ```
+    /// use octopt::Font;
+    ///
+    /// let font = Font::Schip.get_font();
+    /// assert_eq!(font.big_glyph_height, 10);
+    /// assert_eq!(font.big_glyph_range, Some(0..=9));
+    /// ```
+    pub fn get_font(&self) -> FontData {
+        let (small, big) = self.get_font_data();
+        let big_glyph_height = big.as_ref().map_or(0, |big| {
+            big.chunks(10)
+                .filter_map(|glyph| glyph.iter().rposition(|&row| row != 0))
+                .map(|last_row| last_row as u8 + 1)
+                .max()
+                .unwrap_or(0)
+        });
+        let big_glyph_range = big.as_ref().and_then(|big| {
+            let last_glyph = u8::try_from(big.len() / 10).ok()?.checked_sub(1)?;
+            Some(0..=last_glyph)
+        });
+        FontData {
+            small,
+            big,
+            big_glyph_height,
+            big_glyph_range,
         }
     }
 }
+
+/// The sprite data for a [Font], along with a description of its big digits, which vary between
+/// fonts.
+#[derive(Debug, PartialEq, Clone)]
+pub struct FontData {
+    /// 16 sprites that are 5 bytes tall, one for each hexadecimal digit
+    pub small: [u8; 5 * 16],
+    /// Sprites that are 10 bytes tall, one for each digit in `big_glyph_range`, if the font has
+    /// big digits
+    pub big: Option<Vec<u8>>,
+    /// How many of the 10 bytes of each big digit are actually used; some fonts pad smaller
+    /// digits to 10 bytes. 0 if the font has no big digits.
+    pub big_glyph_height: u8,
+    /// Which digits the font has big versions of, like `0..=9` for SUPER-CHIP's font. `None` if
+    /// the font has no big digits.
+    pub big_glyph_range: Option<RangeInclusive<u8>>,
+}
+
+/// How two fonts differ, as reported by [`Font::compare`].
+#[derive(Debug, PartialEq, Clone)]
+pub struct FontComparison {
+    /// Whether the small digits of the fonts are identical
+    pub small_identical: bool,
+    /// Whether the big digits of the fonts are identical, or `None` if either font has no big
+    /// digits to compare
+    pub big_identical: Option<bool>,
+    /// The hexadecimal digits whose small sprites differ, in ascending order
+    pub differing_small: Vec<u8>,
+}
@@ -0,0 +1,218 @@
+// Reading Octocarts, the GIF images Octo saves games in. The game is stored as JSON in the
+// frames of the image, in the lowest two bits of each pixel's color index, four pixels to a byte
+// with the first pixel in the highest bits. The JSON is preceded by its length, as four bytes in
+// big-endian order.
+
+// The most pixels that are decoded from all the frames together, which is enough for a payload of
+// 4 MiB. Frame sizes come from the file, so this keeps a small file from claiming huge frames.
+const MAX_PIXELS: usize = 1 << 24;
+
+// Returns the JSON stored in an Octocart, or `None` if the bytes aren't a GIF image with a
+// payload.
+pub(crate) fn read_payload(bytes: &[u8]) -> Option<Vec<u8>> {
+    let pixels = decode_gif(bytes, |pixels| {
+        payload_length(pixels)
+            .is_some_and(|length| pixels.len() >= length.saturating_add(4).saturating_mul(4))
+    })?;
+    let length = payload_length(&pixels)?;
+    let payload: Vec<u8> = pixels[4 * 4..]
+        .chunks_exact(4)
+        .take(length)
+        .map(byte_from_pixels)
+        .collect();
+    (payload.len() == length).then_some(payload)
+}
+
+// Returns the length of the payload, once the pixels it's stored in have been decoded
+fn payload_length(pixels: &[u8]) -> Option<usize> {
+    let header: Vec<u8> = pixels
+        .get(..4 * 4)?
+        .chunks_exact(4)
+        .map(byte_from_pixels)
+        .collect();
+    usize::try_from(u32::from_be_bytes(header.try_into().ok()?)).ok()
+}
+
+fn byte_from_pixels(pixels: &[u8]) -> u8 {
+    pixels.iter().fold(0, |byte, pixel| byte << 2 | pixel & 3)
+}
+
+// Returns the color indices of the pixels of the frames in a GIF image, one frame after the other,
+// until `done` returns true for the pixels decoded so far. Interlaced frames aren't supported,
+// since Octo doesn't write them, and nor are images with more than `MAX_PIXELS` pixels in total.
+fn decode_gif(bytes: &[u8], done: impl Fn(&[u8]) -> bool) -> Option<Vec<u8>> {
+    let mut reader = Reader(bytes);
+    if !matches!(reader.take(6)?, b"GIF87a" | b"GIF89a") {
+        return None;
+    }
+    // The size of the image, which the frames are assumed to fill
+    reader.take(4)?;
+    let flags = reader.u8()?;
+    // The background color and pixel aspect ratio
+    reader.take(2)?;
+    reader.skip_color_table(flags)?;
+
+    let mut pixels = Vec::new();
+    let mut area = 0;
+    loop {
+        match reader.u8()? {
+            // Extensions, like the delay between frames
+            0x21 => {
+                reader.u8()?;
+                reader.sub_blocks()?;
+            }
+            // A frame
+            0x2C => {
+                // Where the frame is in the image
+                reader.take(4)?;
+                let width = reader.u16()?;
+                let height = reader.u16()?;
+                let flags = reader.u8()?;
+                if flags & 0x40 != 0 {
+                    return None;
+                }
+                let frame_area = usize::from(width) * usize::from(height);
+                area += frame_area;
+                if area > MAX_PIXELS {
+                    return None;
+                }
+                reader.skip_color_table(flags)?;
+                let min_code_size = reader.u8()?;
+                let data = reader.sub_blocks()?;
+                decode_lzw(&data, min_code_size, pixels.len() + frame_area, &mut pixels)?;
+                if done(&pixels) {
+                    return Some(pixels);
+                }
+            }
+            // The end of the image
+            0x3B => return Some(pixels),
+            _ => return None,
+        }
+    }
+}
+
+// Decompresses the LZW-compressed color indices of a frame, as described in appendix F of the
+// GIF89a specification, and appends them to `pixels`, up to `max_pixels` in all.
+fn decode_lzw(
+    data: &[u8],
+    min_code_size: u8,
+    max_pixels: usize,
+    pixels: &mut Vec<u8>,
+) -> Option<()> {
+    if !(2..=8).contains(&min_code_size) {
+        return None;
+    }
+    let clear = 1 << min_code_size;
+    let end = clear + 1;
+    // Each code stands for the string of pixels of an earlier code followed by one more pixel.
+    // The codes for single pixels have no earlier code, and nor do `clear` and `end`.
+    let mut table: Vec<(Option<usize>, u8)> = (0..=end).map(|code| (None, code as u8)).collect();
+    let mut code_size = min_code_size + 1;
+    let mut previous = None;
+
+    let mut data = data.iter();
+    let (mut bits, mut bit_count) = (0u32, 0);
+    while pixels.len() < max_pixels {
+        while bit_count < code_size {
+            // Some encoders leave out the end code
+            let Some(&byte) = data.next() else {
+                break;
+            };
+            bits |= u32::from(byte) << bit_count;
+            bit_count += 8;
+        }
+        if bit_count < code_size {
+            break;
+        }
+        let code = (bits & ((1 << code_size) - 1)) as usize;
+        bits >>= code_size;
+        bit_count -= code_size;
+
+        if code == clear {
+            table.truncate(end + 1);
+            code_size = min_code_size + 1;
+            previous = None;
+            continue;
+        }
+        if code == end {
+            break;
+        }
+        let start = pixels.len();
+        match previous {
+            _ if code < table.len() => push_pixels(&table, code, pixels),
+            // The code that's about to be added, which is the previous string followed by its own
+            // first pixel
+            Some(previous) if code == table.len() => {
+                push_pixels(&table, previous, pixels);
+                pixels.push(pixels[start]);
+            }
+            _ => return None,
+        }
+        if let Some(previous) = previous {
+            if table.len() < 1 << 12 {
+                table.push((Some(previous), pixels[start]));
+            }
+        }
+        previous = Some(code);
+        if table.len() == 1 << code_size && code_size < 12 {
+            code_size += 1;
+        }
+    }
+    pixels.truncate(max_pixels);
+    Some(())
+}
+
+// Appends the string of pixels that a code stands for.
+fn push_pixels(table: &[(Option<usize>, u8)], code: usize, pixels: &mut Vec<u8>) {
+    let start = pixels.len();
+    let mut code = Some(code);
+    while let Some(index) = code {
+        let (earlier, pixel) = table[index];
+        pixels.push(pixel);
+        code = earlier;
+    }
+    pixels[start..].reverse();
+}
+
+struct Reader<'a>(&'a [u8]);
+
+impl<'a> Reader<'a> {
+    fn take(&mut self, count: usize) -> Option<&'a [u8]> {
+        if count > self.0.len() {
+            return None;
+        }
+        let (taken, rest) = self.0.split_at(count);
+        self.0 = rest;
+        Some(taken)
+    }
+
+    fn u8(&mut self) -> Option<u8> {
+        Some(self.take(1)?[0])
+    }
+
+    fn u16(&mut self) -> Option<u16> {
+        let bytes = self.take(2)?;
+        Some(u16::from_le_bytes([bytes[0], bytes[1]]))
+    }
+
+    // Skips the color table that follows a block with the given flags, if it has one
+    fn skip_color_table(&mut self, flags: u8) -> Option<()> {
+        if flags & 0x80 != 0 {
+            self.take(3 << ((flags & 0x07) + 1))?;
+        }
+        Some(())
+    }
+
+    // Reads data split into blocks of at most 255 bytes, each preceded by its length and ending
+    // with an empty block
+    fn sub_blocks(&mut self) -> Option<Vec<u8>> {
+        let mut data = Vec::new();
+        loop {
+            let length = self.u8()?;
+            if length == 0 {
+                return Some(data);
+            }
+            data.extend_from_slice(self.take(usize::from(length))?);
+        }
+    }
+}
@@ -0,0 +1,206 @@
+use crate::{strip_bom, Options, OptionsField, ParseError};
+use serde::de::{Deserializer, MapAccess, Visitor};
+use serde::Deserialize;
+use serde_json::Value;
+use std::fmt;
+use std::str::FromStr;
+
+/// Options in JSON that remember how each value was written and the order of the keys, so they
+/// can be updated with new [Options] without rewriting values that didn't change, like quirks
+/// written as `0` and `1` rather than `false` and `true`. Keys that aren't options are kept as
+/// they are. Whitespace isn't kept; the JSON is always written compactly, and the keys of nested
+/// objects, like `quirks`, are written in alphabetical order.
+///
+/// Example:
+/// ```
+/// use octopt::RawOptions;
+///
+/// let mut raw: RawOptions = r#"{"tickrate":20,"shiftQuirks":1}"#.parse().unwrap();
+/// let mut options = raw.options().unwrap();
+/// options.tickrate = Some(30);
+/// raw.set_options(options);
+/// assert_eq!(raw.to_string(), r#"{"tickrate":30,"shiftQuirks":1}"#);
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct RawOptions {
+    entries: Vec<(String, Value)>,
+}
+
+impl RawOptions {
+    /// Deserializes the Options in this JSON.
+    ///
+    /// # Errors
+    ///
+    /// Returns an `Err` if deserialization from the JSON failed.
+    pub fn options(&self) -> Result<Options, ParseError> {
+        self.to_string().parse()
+    }
+
+    /// Replaces the values in this JSON with the given Options. Values that mean the same as
+    /// before are left exactly as they were written, and changed values are written in the same
+    /// form as the old ones where possible, so `1` stays a number rather than becoming `true`.
+    /// Keys that are already in the JSON keep their position and name, even if it's an alias like
+    /// `max_rom`, and so do the keys in nested `quirks` and `colors` objects. Keys that aren't in
+    /// the JSON yet are added at the end, unless their values are the defaults, and keys for
+    /// values that aren't set in `options` are removed.
+    pub fn set_options(&mut self, options: Options) {
+        let mut values: Vec<(String, Value)> = match serde_json::to_value(options) {
+            Ok(Value::Object(values)) => values.into_iter().collect(),
+            _ => Vec::new(),
+        };
+        let empty = Options::deserialize(Value::Object(serde_json::Map::new())).unwrap_or_default();
+
+        self.entries.retain_mut(|(key, old_value)| {
+            // Newer tools nest the quirks and colors, which are updated one by one like the rest
+            if let (Some(nested_field), Value::Object(nested)) =
+                (nested_field_for(key), &mut *old_value)
+            {
+                nested.retain(|key, old_value| match nested_field(key) {
+                    Some(field) => update_entry(field, old_value, &mut values, &empty),
+                    None => true,
+                });
+                return true;
+            }
+            match field_for(key) {
+                Some(field) => update_entry(field, old_value, &mut values, &empty),
+                // Keep keys that aren't options, like metadata
+                None => true,
+            }
+        });
+        // Values that are the same as leaving the key out, like the default font, aren't added
+        self.entries
+            .extend(values.into_iter().filter(|(key, value)| {
+                match OptionsField::from_json_key(key) {
+                    Some(field) => meaning(field, value, &empty).as_ref() != Some(&empty),
+                    None => true,
+                }
+            }));
+    }
+}
+
+// Replaces the value of an entry for the given field with the field's value in `values`, which is
+// removed from them. Returns false if the field isn't set there, so the entry should be removed.
+// The key might be an alias, like `max_rom` for `maxSize`, so the value is looked up by the key the
+// field is serialized with.
+fn update_entry(
+    field: OptionsField,
+    old_value: &mut Value,
+    values: &mut Vec<(String, Value)>,
+    empty: &Options,
+) -> bool {
+    let Some(index) = values.iter().position(|(key, _)| key == field.json_key()) else {
+        return false;
+    };
+    let (_, value) = values.remove(index);
+    if meaning(field, old_value, empty) != meaning(field, &value, empty) {
+        *old_value = in_form_of(old_value, value);
+    }
+    true
+}
+
+// Sets the given field of otherwise empty Options to a JSON value, for finding out what the value
+// means. Returns `None` if the value is invalid.
+fn meaning(field: OptionsField, value: &Value, empty: &Options) -> Option<Options> {
+    let mut options = empty.clone();
+    let value = match value {
+        Value::String(value) => value.clone(),
+        value => value.to_string(),
+    };
+    options.set_field_str(field, &value).ok()?;
+    Some(options)
+}
+
+// Returns the field that a key at the top level is for. The short names of the quirks, like
+// `shift`, are only used in a nested `quirks` object, so at the top level they aren't options.
+fn field_for(key: &str) -> Option<OptionsField> {
+    OptionsField::from_json_key(key)
+        .filter(|field| !field.is_quirk() || field.json_key().eq_ignore_ascii_case(key))
+}
+
+// Returns how the keys of a nested object with the given key, like `quirks`, map to fields.
+fn nested_field_for(key: &str) -> Option<fn(&str) -> Option<OptionsField>> {
+    match key {
+        "quirks" => Some(|key| OptionsField::from_json_key(key).filter(|field| field.is_quirk())),
+        "colors" => Some(|key| {
+            Some(match key {
+                "fill" => OptionsField::FillColor,
+                "fill2" => OptionsField::FillColor2,
+                "blend" => OptionsField::BlendColor,
+                "background" => OptionsField::BackgroundColor,
+                "buzz" | "buzzer" => OptionsField::BuzzColor,
+                "quiet" | "silence" => OptionsField::QuietColor,
+                "backgroundTransparent" => OptionsField::BackgroundTransparent,
+                _ => return None,
+            })
+        }),
+        _ => None,
+    }
+}
+
+// Converts a new value into the form of the old value it replaces, if they're different forms of
+// the same type of value.
+fn in_form_of(old_value: &Value, value: Value) -> Value {
+    match (old_value, value) {
+        (Value::Number(_), Value::Bool(value)) => Value::from(u8::from(value)),
+        (Value::String(_), Value::Number(value)) => Value::from(value.to_string()),
+        (_, value) => value,
+    }
+}
+
+// A JSON object's entries, in the order they were written.
+struct Entries(Vec<(String, Value)>);
+
+impl<'de> Deserialize<'de> for Entries {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        struct EntriesVisitor;
+
+        impl<'de> Visitor<'de> for EntriesVisitor {
+            type Value = Entries;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                formatter.write_str("a JSON object")
+            }
+
+            fn visit_map<A>(self, mut map: A) -> Result<Self::Value, A::Error>
+            where
+                A: MapAccess<'de>,
+            {
+                let mut entries = Vec::new();
+                while let Some(entry) = map.next_entry()? {
+                    entries.push(entry);
+                }
+                Ok(Entries(entries))
+            }
+        }
+
+        deserializer.deserialize_map(EntriesVisitor)
+    }
+}
+
+/// Parses Options from a JSON string, remembering how they were written.
+impl FromStr for RawOptions {
+    type Err = ParseError;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = strip_bom(s);
+        Options::from_str(s)?;
+        let Entries(entries) = serde_json::from_str(s).map_err(ParseError::Json)?;
+        Ok(Self { entries })
+    }
+}
+
+/// Serializes the Options as compact JSON, with the values written as they were read.
+impl fmt::Display for RawOptions {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{{")?;
+        for (index, (key, value)) in self.entries.iter().enumerate() {
+            if index > 0 {
+                write!(f, ",")?;
+            }
+            write!(f, "{}:{}", Value::from(key.as_str()), value)?;
+        }
+        write!(f, "}}")
+    }
+}
@@ -0,0 +1,192 @@
+use crate::{ClipMode, Font, LoResDxy0Behavior, LoadStoreMode, Options, ScreenRotation, TouchMode};
+use serde::Serialize;
+use serde_json::{json, Map, Value};
+
+const QUIRK_KEYS: [&str; 11] = [
+    "shiftQuirks",
+    "jumpQuirks",
+    "logicQuirks",
+    "vBlankQuirks",
+    "vfOrderQuirks",
+    "resClearQuirks",
+    "delayWrapQuirks",
+    "hiresCollisionQuirks",
+    "clipCollisionQuirks",
+    "scrollQuirks",
+    "overflowIQuirks",
+];
+
+const COLOR_KEYS: [&str; 6] = [
+    "fillColor",
+    "fillColor2",
+    "blendColor",
+    "backgroundColor",
+    "buzzColor",
+    "quietColor",
+];
+
+// The tokens are taken from serde itself, so they can't drift from what we actually emit.
+fn enum_tokens<T: Serialize>(variants: &[T]) -> Value {
+    Value::Array(
+        variants
+            .iter()
+            .map(|variant| serde_json::to_value(variant).unwrap_or(Value::Null))
+            .collect(),
+    )
+}
+
+// Booleans written as strings, in any case, the way `bool_from_str` reads them.
+fn bool_string_schema() -> Value {
+    let words: Vec<String> = ["0", "1", "true", "false", "on", "off"]
+        .iter()
+        .map(|word| {
+            word.chars()
+                .map(|c| match c {
+                    'a'..='z' => format!("[{}{}]", c, c.to_ascii_uppercase()),
+                    c => c.to_string(),
+                })
+                .collect()
+        })
+        .collect();
+    json!({ "type": "string", "pattern": format!("^\\s*({})\\s*$", words.join("|")) })
+}
+
+// Numbers that may be fractional, like `20.0`, are rounded when they're read, so any number that
+// rounds to a u16 is allowed.
+fn u16_schema() -> Value {
+    json!({
+        "oneOf": [
+            { "type": "number", "minimum": 0, "maximum": u16::MAX },
+            { "type": "string", "pattern": "^([0-9]+|0[xX][0-9a-fA-F]+)$" }
+        ]
+    })
+}
+
+impl Options {
+    /// Returns a [JSON Schema](https://json-schema.org/) that describes the JSON format of
+    /// Options, which can be used to validate configuration files without using this library.
+    pub fn json_schema() -> Value {
+        let mut properties = Map::new();
+        properties.insert("tickrate".into(), u16_schema());
+        properties.insert("maxSize".into(), u16_schema());
+        properties.insert("startAddress".into(), u16_schema());
+        properties.insert("buzzerPitch".into(), u16_schema());
+        properties.insert("fontAddress".into(), u16_schema());
+        properties.insert(
+            "customFont".into(),
+            json!({
+                "type": "object",
+                "properties": {
+                    "small": { "type": "string", "contentEncoding": "base64" },
+                    "big": { "type": "string", "contentEncoding": "base64" }
+                },
+                "required": ["small"]
+            }),
+        );
+        properties.insert(
+            "planeCount".into(),
+            json!({ "type": "integer", "minimum": 1, "maximum": 4 }),
+        );
+        properties.insert(
+            "screenRotation".into(),
+            json!({ "enum": enum_tokens(&[
+                ScreenRotation::Normal,
+                ScreenRotation::ClockWise,
+                ScreenRotation::UpsideDown,
+                ScreenRotation::CounterClockWise,
+            ]) }),
+        );
+        let fonts = json!({ "enum": enum_tokens(&[
+            Font::Octo,
+            Font::Vip,
+            Font::Dream6800,
+            Font::Eti660,
+            Font::Schip,
+            Font::Fish,
+            Font::AKouZ1,
+            Font::None,
+        ]) });
+        properties.insert("fontStyle".into(), fonts.clone());
+        properties.insert("fontBigStyle".into(), fonts);
+        properties.insert(
+            "touchInputMode".into(),
+            json!({ "enum": enum_tokens(&[
+                TouchMode::None,
+                TouchMode::Swipe,
+                TouchMode::Seg16,
+                TouchMode::Seg16Fill,
+                TouchMode::Gamepad,
+                TouchMode::Vip,
+            ]) }),
+        );
+        for key in COLOR_KEYS {
+            properties.insert(
+                key.into(),
+                json!({ "oneOf": [
+                    { "type": "string" },
+                    { "type": "integer", "minimum": 0, "maximum": 0xFF_FFFF },
+                ] }),
+            );
+        }
+        properties.insert("backgroundTransparent".into(), json!({ "type": "boolean" }));
+        for key in QUIRK_KEYS {
+            properties.insert(
+                key.into(),
+                json!({ "oneOf": [
+                    { "type": "boolean" },
+                    { "enum": [0, 1] },
+                    bool_string_schema(),
+                    { "type": "null" },
+                ] }),
+            );
+        }
+        properties.insert(
+            "loadStoreQuirks".into(),
+            json!({ "oneOf": [
+                { "type": "boolean" },
+                { "enum": [0, 1] },
+                bool_string_schema(),
+                { "type": "null" },
+                { "enum": [
+                    LoadStoreMode::IncrementByXPlus1.to_string(),
+                    LoadStoreMode::IncrementByX.to_string(),
+                    LoadStoreMode::Unchanged.to_string(),
+                ] },
+            ] }),
+        );
+        properties.insert(
+            "clipQuirks".into(),
+            json!({ "oneOf": [
+                { "type": "boolean" },
+                { "enum": [0, 1] },
+                bool_string_schema(),
+                { "type": "null" },
+                { "enum": [
+                    ClipMode::WrapBoth.to_string(),
+                    ClipMode::ClipBoth.to_string(),
+                    ClipMode::ClipXWrapY.to_string(),
+                    ClipMode::WrapXClipY.to_string(),
+                ] },
+            ] }),
+        );
+        properties.insert(
+            "loresDXY0Quirks".into(),
+            json!({ "oneOf": [
+                { "enum": enum_tokens(&[
+                    LoResDxy0Behavior::NoOp,
+                    LoResDxy0Behavior::TallSprite,
+                    LoResDxy0Behavior::BigSprite,
+                ]) },
+                { "type": "null" },
+            ] }),
+        );
+
+        json!({
+            "$schema": "http://json-schema.org/draft-07/schema#",
+            "title": "Options",
+            "description": "CHIP-8 configuration settings, in the format used by Octo",
+            "type": "object",
+            "properties": properties,
+        })
+    }
+}
@@ -0,0 +1,99 @@
+use crate::{some_bool_from_int, ClipMode, LoResDxy0Behavior, LoadStoreMode, Quirks};
+use serde::{Deserialize, Serialize};
+use serde_with::skip_serializing_none;
+
+// The same quirks as `Quirks`, but with the field names used by newer tools, which either nest the
+// quirks in their own object, where the "Quirks" suffix is redundant, or just leave it out.
+#[skip_serializing_none]
+#[derive(Debug, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct ShortQuirks {
+    #[serde(deserialize_with = "some_bool_from_int", default)]
+    shift: Option<bool>,
+    #[serde(default)]
+    load_store: Option<LoadStoreMode>,
+    #[serde(alias = "jump", deserialize_with = "some_bool_from_int", default)]
+    jump0: Option<bool>,
+    #[serde(deserialize_with = "some_bool_from_int", default)]
+    logic: Option<bool>,
+    #[serde(default)]
+    clip: Option<ClipMode>,
+    #[serde(alias = "vBlank", deserialize_with = "some_bool_from_int", default)]
+    vblank: Option<bool>,
+    #[serde(deserialize_with = "some_bool_from_int", default)]
+    vf_order: Option<bool>,
+    #[serde(alias = "loresDXY0")]
+    lores_dxy0: Option<LoResDxy0Behavior>,
+    #[serde(deserialize_with = "some_bool_from_int", default)]
+    res_clear: Option<bool>,
+    #[serde(deserialize_with = "some_bool_from_int", default)]
+    delay_wrap: Option<bool>,
+    #[serde(deserialize_with = "some_bool_from_int", default)]
+    hires_collision: Option<bool>,
+    #[serde(deserialize_with = "some_bool_from_int", default)]
+    clip_collision: Option<bool>,
+    #[serde(deserialize_with = "some_bool_from_int", default)]
+    scroll: Option<bool>,
+    #[serde(deserialize_with = "some_bool_from_int", default)]
+    overflow_i: Option<bool>,
+}
+
+// Octo's name and the short name of each quirk.
+pub(crate) const QUIRK_NAMES: [(&str, &str); 14] = [
+    ("shiftQuirks", "shift"),
+    ("loadStoreQuirks", "loadStore"),
+    ("jumpQuirks", "jump0"),
+    ("logicQuirks", "logic"),
+    ("clipQuirks", "clip"),
+    ("vBlankQuirks", "vblank"),
+    ("vfOrderQuirks", "vfOrder"),
+    ("loresDXY0Quirks", "loresDxy0"),
+    ("resClearQuirks", "resClear"),
+    ("delayWrapQuirks", "delayWrap"),
+    ("hiresCollisionQuirks", "hiresCollision"),
+    ("clipCollisionQuirks", "clipCollision"),
+    ("scrollQuirks", "scroll"),
+    ("overflowIQuirks", "overflowI"),
+];
+
+impl From<ShortQuirks> for Quirks {
+    fn from(quirks: ShortQuirks) -> Self {
+        Self {
+            shift: quirks.shift,
+            load_store: quirks.load_store,
+            jump0: quirks.jump0,
+            logic: quirks.logic,
+            clip: quirks.clip,
+            vblank: quirks.vblank,
+            vf_order: quirks.vf_order,
+            lores_dxy0: quirks.lores_dxy0,
+            res_clear: quirks.res_clear,
+            delay_wrap: quirks.delay_wrap,
+            hires_collision: quirks.hires_collision,
+            clip_collision: quirks.clip_collision,
+            scroll: quirks.scroll,
+            overflow_i: quirks.overflow_i,
+        }
+    }
+}
+
+impl From<Quirks> for ShortQuirks {
+    fn from(quirks: Quirks) -> Self {
+        Self {
+            shift: quirks.shift,
+            load_store: quirks.load_store,
+            jump0: quirks.jump0,
+            logic: quirks.logic,
+            clip: quirks.clip,
+            vblank: quirks.vblank,
+            vf_order: quirks.vf_order,
+            lores_dxy0: quirks.lores_dxy0,
+            res_clear: quirks.res_clear,
+            delay_wrap: quirks.delay_wrap,
+            hires_collision: quirks.hires_collision,
+            clip_collision: quirks.clip_collision,
+            scroll: quirks.scroll,
+            overflow_i: quirks.overflow_i,
+        }
+    }
+}
@@ -0,0 +1,123 @@
+//! Implementations of [`clap::ValueEnum`], so the enums can be used as command-line arguments.
+//!
+//! The possible values are the same tokens used when serializing the enums.
+
+use crate::{Font, LoResDxy0Behavior, Platform, ScreenRotation, TouchMode};
+use clap::builder::PossibleValue;
+use clap::ValueEnum;
+
+impl ValueEnum for Platform {
+    fn value_variants<'a>() -> &'a [Self] {
+        &[
+            Platform::Octo,
+            Platform::Vip,
+            Platform::Dream6800,
+            Platform::Eti660,
+            Platform::Chip48,
+            Platform::Schip,
+            Platform::XoChip,
+        ]
+    }
+
+    fn to_possible_value(&self) -> Option<PossibleValue> {
+        Some(PossibleValue::new(match self {
+            Platform::Octo => "octo",
+            Platform::Vip => "vip",
+            Platform::Dream6800 => "dream6800",
+            Platform::Eti660 => "eti660",
+            Platform::Chip48 => "chip48",
+            Platform::Schip => "schip",
+            Platform::XoChip => "xochip",
+        }))
+    }
+}
+
+impl ValueEnum for TouchMode {
+    fn value_variants<'a>() -> &'a [Self] {
+        &[
+            TouchMode::None,
+            TouchMode::Swipe,
+            TouchMode::Seg16,
+            TouchMode::Seg16Fill,
+            TouchMode::Gamepad,
+            TouchMode::Vip,
+        ]
+    }
+
+    fn to_possible_value(&self) -> Option<PossibleValue> {
+        Some(PossibleValue::new(match self {
+            TouchMode::None => "none",
+            TouchMode::Swipe => "swipe",
+            TouchMode::Seg16 => "seg16",
+            TouchMode::Seg16Fill => "seg16fill",
+            TouchMode::Gamepad => "gamepad",
+            TouchMode::Vip => "vip",
+        }))
+    }
+}
+
+impl ValueEnum for LoResDxy0Behavior {
+    fn value_variants<'a>() -> &'a [Self] {
+        &[
+            LoResDxy0Behavior::NoOp,
+            LoResDxy0Behavior::TallSprite,
+            LoResDxy0Behavior::BigSprite,
+        ]
+    }
+
+    fn to_possible_value(&self) -> Option<PossibleValue> {
+        Some(PossibleValue::new(match self {
+            LoResDxy0Behavior::NoOp => "no_op",
+            LoResDxy0Behavior::TallSprite => "tall_sprite",
+            LoResDxy0Behavior::BigSprite => "big_sprite",
+        }))
+    }
+}
+
+impl ValueEnum for ScreenRotation {
+    fn value_variants<'a>() -> &'a [Self] {
+        &[
+            ScreenRotation::Normal,
+            ScreenRotation::ClockWise,
+            ScreenRotation::UpsideDown,
+            ScreenRotation::CounterClockWise,
+        ]
+    }
+
+    fn to_possible_value(&self) -> Option<PossibleValue> {
+        Some(PossibleValue::new(match self {
+            ScreenRotation::Normal => "0",
+            ScreenRotation::ClockWise => "90",
+            ScreenRotation::UpsideDown => "180",
+            ScreenRotation::CounterClockWise => "270",
+        }))
+    }
+}
+
+impl ValueEnum for Font {
+    fn value_variants<'a>() -> &'a [Self] {
+        &[
+            Font::Octo,
+            Font::Vip,
+            Font::Dream6800,
+            Font::Eti660,
+            Font::Schip,
+            Font::Fish,
+            Font::AKouZ1,
+            Font::None,
+        ]
+    }
+
+    fn to_possible_value(&self) -> Option<PossibleValue> {
+        Some(match self {
+            Font::Octo => PossibleValue::new("octo"),
+            Font::Vip => PossibleValue::new("vip"),
+            Font::Dream6800 => PossibleValue::new("dream_6800").alias("dream6800"),
+            Font::Eti660 => PossibleValue::new("eti_660").alias("eti660"),
+            Font::Schip => PossibleValue::new("schip"),
+            Font::Fish => PossibleValue::new("fish"),
+            Font::AKouZ1 => PossibleValue::new("akouz1"),
+            Font::None => PossibleValue::new("none"),
+        })
+    }
+}
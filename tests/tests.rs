@@ -2,7 +2,13 @@
 
 #[cfg(test)]
 use assert_json_diff::assert_json_eq;
-use octopt::{Font, Options, Platform};
+use octopt::color::Color;
+use octopt::{
+    CartMetadata, ClipMode, ColorRole, Colors, CustomFont, EmulatorCaps, Font, FontLayout,
+    LayoutError, Lint, LoResDxy0Behavior, LoadStoreMode, OctoVersion, Options, OptionsField,
+    ParseError, Platform, Project, Quirks, QuirksBuilder, RawIni, RawOptions, ResolvedQuirks,
+    RotationScope, ScreenRotation, SetError, Severity, TouchMode, Warning,
+};
 use reqwest::blocking;
 use serde_json::{json, Value};
 
@@ -44,6 +50,25 @@ fn chip8_archive() {
     for (_, program) in programs.as_object().unwrap() {
         let _: Options = program["options"].to_string().parse().unwrap();
     }
+    let archive = Options::parse_archive(&body).unwrap();
+    assert_eq!(archive.len(), programs.as_object().unwrap().len());
+}
+
+#[test]
+fn parse_archive() {
+    let programs = json!({
+        "first": {"title":"First","options":{"tickrate":20,"shiftQuirks":1}},
+        "second": {"title":"Second","options":{"tickrate":"30","fontStyle":"vip"}},
+        "broken": {"title":"Broken","options":{"shiftQuirks":2}},
+        "missing": {"title":"Missing"}
+    });
+    let archive = Options::parse_archive(&programs.to_string()).unwrap();
+    assert_eq!(archive.len(), 2);
+    assert_eq!(archive["first"].tickrate, Some(20));
+    assert_eq!(archive["first"].quirks.shift, Some(true));
+    assert_eq!(archive["second"].tickrate, Some(30));
+    assert_eq!(archive["second"].font_style, Font::Vip);
+    assert!(Options::parse_archive("[]").is_err());
 }
 
 /// Downloads the default .octo.rc from the C-Octo repo and parses it
@@ -86,3 +111,2120 @@ fn octo_get_font_data() {
     let octo_defaults = Options::default();
     Font::get_font_data(&octo_defaults.font_style);
 }
+
+/// Parses an .octo.rc with a distinct value for every color key, and checks that each one ends up
+/// in the right field.
+#[test]
+fn octo_rc_colors() {
+    let ini = "colors.plane0=000001\r\ncolors.plane1=000002\r\ncolors.plane2=000003\r\ncolors.plane3=000004\r\ncolors.background=000005\r\ncolors.sound=000006\r\n";
+    let options = Options::from_ini(ini).unwrap();
+    assert_eq!(
+        options.colors.background_color,
+        Some(Color { r: 0, g: 0, b: 1 })
+    );
+    assert_eq!(options.colors.fill_color, Some(Color { r: 0, g: 0, b: 2 }));
+    assert_eq!(options.colors.fill_color2, Some(Color { r: 0, g: 0, b: 3 }));
+    assert_eq!(options.colors.blend_color, Some(Color { r: 0, g: 0, b: 4 }));
+    assert_eq!(options.colors.quiet_color, Some(Color { r: 0, g: 0, b: 5 }));
+    assert_eq!(options.colors.buzz_color, Some(Color { r: 0, g: 0, b: 6 }));
+}
+
+#[test]
+fn octo_rc_colors_round_trip() {
+    let ini = "colors.plane0=000001\r\ncolors.plane1=000002\r\ncolors.plane2=000003\r\ncolors.plane3=000004\r\ncolors.background=000005\r\ncolors.sound=000006\r\n";
+    let options = Options::from_ini(ini).unwrap();
+    let round_tripped = Options::from_ini(&options.to_ini()).unwrap();
+    assert_eq!(Options::from_ini(ini).unwrap().colors, round_tripped.colors);
+}
+
+#[test]
+fn cart_metadata() {
+    let entry = json!({"title":"Octojam 1 Title","authors":["John Earnest","Someone Else"],"desc":"A title screen.","event":"Octojam 1","options":{"tickrate":20,"shiftQuirks":false}});
+    let metadata: CartMetadata = entry.to_string().parse().unwrap();
+    assert_eq!(metadata.name.as_deref(), Some("Octojam 1 Title"));
+    assert_eq!(metadata.description.as_deref(), Some("A title screen."));
+    assert_eq!(
+        metadata.author.as_deref(),
+        Some("John Earnest, Someone Else")
+    );
+    assert_eq!(metadata.options.tickrate, Some(20));
+    assert_eq!(metadata.options.quirks.shift, Some(false));
+}
+
+#[test]
+fn cart_metadata_missing_fields() {
+    let metadata: CartMetadata = json!({"options":{"tickrate":20}})
+        .to_string()
+        .parse()
+        .unwrap();
+    assert_eq!(metadata.name, None);
+    assert_eq!(metadata.description, None);
+    assert_eq!(metadata.author, None);
+    assert_eq!(metadata.options.tickrate, Some(20));
+}
+
+#[test]
+fn cart_metadata_platform() {
+    let entry = json!({"platform":"xochip","options":{"tickrate":1000,"shiftQuirks":true}});
+    let metadata: CartMetadata = entry.to_string().parse().unwrap();
+    assert_eq!(metadata.platform, Some(Platform::XoChip));
+    let mut expected = Quirks::from(Platform::XoChip);
+    expected.shift = Some(true);
+    assert_eq!(metadata.resolve_quirks(), expected.resolve());
+
+    let programs = json!({"game": entry, "other": {"platform":"someday","options":{}}});
+    let archive = Options::parse_archive(&programs.to_string()).unwrap();
+    assert_eq!(archive["game"].quirks, expected);
+    assert_eq!(archive["other"].quirks.shift, None);
+}
+
+#[test]
+fn max_size_aliases() {
+    let options: Options = json!({"max_rom": 3584}).to_string().parse().unwrap();
+    assert_eq!(options.max_size, Some(3584));
+    let options: Options = json!({"maxSize": 3584}).to_string().parse().unwrap();
+    assert_eq!(options.max_size, Some(3584));
+    let options = Options::from_ini("core.maxSize=3216\r\n").unwrap();
+    assert_eq!(options.max_size, Some(3216));
+    let options = Options::from_ini("core.max_rom=3216\r\n").unwrap();
+    assert_eq!(options.max_size, Some(3216));
+}
+
+#[test]
+fn color_nearest_in() {
+    let palette = [
+        Color { r: 0, g: 0, b: 0 },
+        Color { r: 255, g: 0, b: 0 },
+        Color {
+            r: 255,
+            g: 255,
+            b: 255,
+        },
+    ];
+    for color in palette {
+        assert_eq!(color.nearest_in(&palette), color);
+    }
+    assert_eq!(
+        Color {
+            r: 200,
+            g: 30,
+            b: 10
+        }
+        .nearest_in(&palette),
+        Color { r: 255, g: 0, b: 0 }
+    );
+    assert_eq!(
+        Color { r: 1, g: 2, b: 3 }.nearest_in(&[]),
+        Color { r: 1, g: 2, b: 3 }
+    );
+}
+
+#[test]
+fn colors_quantize() {
+    let palette = [
+        Color { r: 0, g: 0, b: 0 },
+        Color {
+            r: 255,
+            g: 255,
+            b: 255,
+        },
+    ];
+    let mut colors = Colors::default();
+    colors.quantize(&palette);
+    assert_eq!(
+        colors.fill_color,
+        Some(Color {
+            r: 255,
+            g: 255,
+            b: 255
+        })
+    );
+    assert_eq!(
+        colors.fill_color2,
+        Some(Color {
+            r: 255,
+            g: 255,
+            b: 255
+        })
+    );
+    assert_eq!(colors.background_color, Some(Color { r: 0, g: 0, b: 0 }));
+    assert_eq!(colors.quiet_color, Some(Color { r: 0, g: 0, b: 0 }));
+}
+
+#[test]
+fn json_schema() {
+    let schema = Options::json_schema();
+    let properties = schema["properties"].as_object().unwrap();
+    let mut options = Options::default();
+    options.font_big_style = Some(Font::Schip);
+    options.plane_count = Some(4);
+    options.buzzer_pitch = Some(440);
+    options.font_address = Some(0x50);
+    options.custom_font = Some(CustomFont::default());
+    options.colors.background_transparent = Some(true);
+    let serialized = serde_json::to_value(options).unwrap();
+    for key in serialized.as_object().unwrap().keys() {
+        assert!(properties.contains_key(key), "{} missing from schema", key);
+    }
+    assert_eq!(properties.len(), serialized.as_object().unwrap().len());
+    assert!(properties["fontStyle"]["enum"]
+        .as_array()
+        .unwrap()
+        .contains(&json!("dream_6800")));
+    assert!(properties["touchInputMode"]["enum"]
+        .as_array()
+        .unwrap()
+        .contains(&json!("seg16fill")));
+    assert_eq!(
+        properties["screenRotation"]["enum"],
+        json!([0, 90, 180, 270])
+    );
+    for key in ["shiftQuirks", "loadStoreQuirks", "clipQuirks"] {
+        assert!(properties[key]["oneOf"]
+            .as_array()
+            .unwrap()
+            .contains(&json!({
+                "type": "string",
+                "pattern": "^\\s*(0|1|[tT][rR][uU][eE]|[fF][aA][lL][sS][eE]|[oO][nN]|[oO][fF][fF])\\s*$"
+            })));
+    }
+    assert_eq!(properties["tickrate"]["oneOf"][0]["type"], json!("number"));
+    assert_eq!(
+        properties["fillColor"]["oneOf"][1],
+        json!({ "type": "integer", "minimum": 0, "maximum": 0xFF_FFFF })
+    );
+    for key in [
+        "shiftQuirks",
+        "loadStoreQuirks",
+        "clipQuirks",
+        "loresDXY0Quirks",
+    ] {
+        assert!(properties[key]["oneOf"]
+            .as_array()
+            .unwrap()
+            .contains(&json!({ "type": "null" })));
+    }
+    assert!(properties["clipQuirks"]["oneOf"][4]["enum"]
+        .as_array()
+        .unwrap()
+        .contains(&json!("clip_both")));
+}
+
+#[test]
+fn options_font_data() {
+    let mut options = Options::default();
+    assert_eq!(options.get_font_data(), Font::Octo.get_font_data());
+
+    options.font_style = Font::Schip;
+    options.font_big_style = Some(Font::Octo);
+    let (small, big) = options.get_font_data();
+    assert_eq!(small, Font::Schip.get_font_data().0);
+    assert_eq!(big, Font::Octo.get_font_data().1);
+
+    let options: Options = json!({"fontStyle":"schip","fontBigStyle":"octo"})
+        .to_string()
+        .parse()
+        .unwrap();
+    assert_eq!(options.font_style, Font::Schip);
+    assert_eq!(options.font_big_style, Some(Font::Octo));
+}
+
+#[test]
+fn quirks_describe() {
+    assert!(!Quirks::describe("shiftQuirks").unwrap().is_empty());
+    assert_eq!(Quirks::describe("shift"), None);
+    assert_eq!(Quirks::describe("fooQuirks"), None);
+    let serialized = serde_json::to_value(Quirks::default()).unwrap();
+    for key in serialized.as_object().unwrap().keys() {
+        assert!(
+            Quirks::describe(key).is_some(),
+            "{} has no description",
+            key
+        );
+    }
+}
+
+#[test]
+fn screen_rotation_transform() {
+    for (x, y) in [(0, 0), (10, 20), (63, 31)] {
+        assert_eq!(ScreenRotation::Normal.transform(x, y, 64, 32), Some((x, y)));
+    }
+    assert_eq!(
+        ScreenRotation::UpsideDown.transform(0, 0, 64, 32),
+        Some((63, 31))
+    );
+    assert_eq!(
+        ScreenRotation::UpsideDown.transform(63, 31, 64, 32),
+        Some((0, 0))
+    );
+    assert_eq!(
+        ScreenRotation::ClockWise.transform(0, 0, 64, 32),
+        Some((31, 0))
+    );
+    assert_eq!(
+        ScreenRotation::ClockWise.transform(63, 31, 64, 32),
+        Some((0, 63))
+    );
+    assert_eq!(
+        ScreenRotation::CounterClockWise.transform(0, 0, 64, 32),
+        Some((0, 63))
+    );
+    assert_eq!(
+        ScreenRotation::CounterClockWise.transform(63, 31, 64, 32),
+        Some((31, 0))
+    );
+    for rotation in [
+        ScreenRotation::Normal,
+        ScreenRotation::ClockWise,
+        ScreenRotation::UpsideDown,
+        ScreenRotation::CounterClockWise,
+    ] {
+        assert_eq!(rotation.transform(64, 0, 64, 32), None);
+        assert_eq!(rotation.transform(0, 32, 64, 32), None);
+        assert_eq!(rotation.transform(0, 0, 0, 0), None);
+        assert!(rotation
+            .transform(u16::MAX - 1, u16::MAX - 1, u16::MAX, u16::MAX)
+            .is_some());
+    }
+}
+
+#[test]
+fn nested_quirks() {
+    let flat: Options = json!({"shiftQuirks":true}).to_string().parse().unwrap();
+    assert_eq!(flat.quirks.shift, Some(true));
+    let nested: Options = json!({"quirks":{"shift":true}})
+        .to_string()
+        .parse()
+        .unwrap();
+    assert_eq!(nested.quirks.shift, Some(true));
+    assert_eq!(flat, nested);
+
+    let both: Options =
+        json!({"tickrate":20,"quirks":{"shift":1,"vBlank":true},"shiftQuirks":0,"clipQuirks":1})
+            .to_string()
+            .parse()
+            .unwrap();
+    assert_eq!(both.tickrate, Some(20));
+    assert_eq!(both.quirks.shift, Some(true));
+    assert_eq!(both.quirks.vblank, Some(true));
+    assert_eq!(both.quirks.clip, Some(ClipMode::ClipBoth));
+    assert_eq!(both.quirks.jump0, None);
+}
+
+#[test]
+fn colors_to_grayscale() {
+    let grayscale = Colors::default().to_grayscale();
+    assert_eq!(
+        grayscale.fill_color,
+        Some(Color {
+            r: 255,
+            g: 255,
+            b: 255
+        })
+    );
+    assert_eq!(
+        grayscale.blend_color,
+        Some(Color {
+            r: 76,
+            g: 76,
+            b: 76
+        })
+    );
+    assert_eq!(grayscale.to_grayscale(), grayscale);
+    for v in [0, 1, 127, 128, 254, 255] {
+        let gray = Color { r: v, g: v, b: v };
+        assert_eq!(gray.to_grayscale(), gray);
+    }
+}
+
+#[test]
+fn effective_start_address() {
+    let mut options = Options::default();
+    options.start_address = None;
+    assert_eq!(options.effective_start_address(), 512);
+    options.font_style = Font::Eti660;
+    assert_eq!(options.effective_start_address(), 1536);
+    options.start_address = Some(0x300);
+    assert_eq!(options.effective_start_address(), 0x300);
+}
+
+#[cfg(feature = "clap")]
+#[test]
+fn clap_value_enum() {
+    use clap::ValueEnum;
+    use octopt::LoResDxy0Behavior;
+
+    fn assert_serde_tokens<T: ValueEnum + serde::Serialize>() {
+        for variant in T::value_variants() {
+            let token = match serde_json::to_value(variant).unwrap() {
+                Value::String(token) => token,
+                other => other.to_string(),
+            };
+            assert_eq!(variant.to_possible_value().unwrap().get_name(), token);
+        }
+    }
+    assert_serde_tokens::<Font>();
+    assert_serde_tokens::<TouchMode>();
+    assert_serde_tokens::<Platform>();
+    assert_serde_tokens::<LoResDxy0Behavior>();
+    assert_serde_tokens::<ScreenRotation>();
+
+    assert_eq!(Font::from_str("dream_6800", true).unwrap(), Font::Dream6800);
+    assert_eq!(Font::from_str("dream6800", true).unwrap(), Font::Dream6800);
+}
+
+#[test]
+fn font_platform_mismatch() {
+    let mut options = Options::new(Platform::XoChip);
+    assert_eq!(options.font_platform_mismatch(), None);
+    options.font_style = Font::Vip;
+    assert_eq!(
+        options.font_platform_mismatch(),
+        Some(Warning::MissingBigFont)
+    );
+    options.font_big_style = Some(Font::Octo);
+    assert_eq!(options.font_platform_mismatch(), None);
+
+    let mut options = Options::new(Platform::Schip);
+    options.font_style = Font::Dream6800;
+    assert_eq!(
+        options.font_platform_mismatch(),
+        Some(Warning::MissingBigFont)
+    );
+    assert_eq!(Options::new(Platform::Vip).font_platform_mismatch(), None);
+}
+
+#[test]
+fn raw_ini_comments() {
+    let ini = "# My game\r\ncore.tickrate = 20\r\n\r\n; Colors\r\ncolors.plane1=FFCC00\r\nquirks.shift=1 \r\n# The end";
+    let mut raw: RawIni = ini.parse().unwrap();
+    let options = raw.options().unwrap();
+    assert_eq!(options.tickrate, Some(20));
+    assert_eq!(options.quirks.shift, Some(true));
+
+    let mut unchanged = raw.clone();
+    unchanged.set_options(unchanged.options().unwrap());
+    assert!(unchanged.to_string().starts_with("# My game\r\ncore.tickrate = 20\r\n\r\n; Colors\r\ncolors.plane1=FFCC00\r\nquirks.shift=1\r\n# The end\r\n"));
+
+    let mut options = raw.options().unwrap();
+    options.tickrate = Some(30);
+    options.quirks.shift = None;
+    options.quirks.clip = Some(ClipMode::ClipBoth);
+    raw.set_options(options);
+    let updated = raw.to_string();
+    assert!(updated.starts_with(
+        "# My game\r\ncore.tickrate = 30\r\n\r\n; Colors\r\ncolors.plane1=FFCC00\r\n# The end\r\n"
+    ));
+    assert!(updated.contains("quirks.clip=1\r\n"));
+    assert!(!updated.contains("quirks.shift"));
+    assert_eq!(raw.options().unwrap().quirks.clip, Some(ClipMode::ClipBoth));
+}
+
+#[test]
+fn from_platform() {
+    for platform in [
+        Platform::Octo,
+        Platform::Vip,
+        Platform::Dream6800,
+        Platform::Eti660,
+        Platform::Chip48,
+        Platform::Schip,
+        Platform::XoChip,
+    ] {
+        assert_eq!(Options::from(platform), Options::new(platform));
+        assert_eq!(Quirks::from(platform), Options::new(platform).quirks);
+        let options: Options = platform.into();
+        assert_eq!(options, Options::new(platform));
+    }
+}
+
+#[test]
+fn fingerprint() {
+    let options: Options = json!({"tickrate":20,"shiftQuirks":1,"fillColor":"#FFCC00"})
+        .to_string()
+        .parse()
+        .unwrap();
+    let reordered: Options = json!({"fillColor":"FFCC00","shiftQuirks":true,"tickrate":"20"})
+        .to_string()
+        .parse()
+        .unwrap();
+    assert_eq!(options.fingerprint(), reordered.fingerprint());
+
+    let mut changed = options.clone();
+    changed.quirks.clip = Some(ClipMode::ClipBoth);
+    assert!(!options.semantically_eq(&changed));
+    assert_ne!(options.fingerprint(), changed.fingerprint());
+
+    let mut resolved = options.clone();
+    resolved.quirks.clip = Some(ClipMode::WrapBoth);
+    resolved.colors.fill_color2 = Colors::default().fill_color2;
+    assert!(options.semantically_eq(&resolved));
+    assert_eq!(options.fingerprint(), resolved.fingerprint());
+}
+
+#[test]
+fn font_blob() {
+    let (small, big) = Font::Schip.get_font_data();
+    let blob = Font::Schip.get_font_blob(0x50, Some(0x100));
+    assert_eq!(blob.len(), small.len() + big.as_ref().unwrap().len());
+    for (i, (address, byte)) in blob[..80].iter().enumerate() {
+        assert_eq!(*address, 0x50 + i as u16);
+        assert_eq!(*byte, small[i]);
+    }
+    assert_eq!(blob[80], (0x100, big.unwrap()[0]));
+
+    assert_eq!(Font::Schip.get_font_blob(0, None).len(), 80);
+    assert_eq!(Font::Vip.get_font_blob(0, Some(0x100)).len(), 80);
+}
+
+#[test]
+fn quirks_inverted() {
+    let quirks = Quirks::from(Platform::Vip);
+    let inverted = quirks.inverted();
+    assert_eq!(inverted.shift, Some(true));
+    assert_eq!(inverted.logic, Some(false));
+    assert_eq!(inverted.scroll, None);
+    assert_eq!(inverted.lores_dxy0, quirks.lores_dxy0);
+    assert_eq!(inverted.inverted(), quirks);
+}
+
+#[test]
+fn plane_count() {
+    let mut options = Options::default();
+    assert_eq!(options.effective_plane_count(), 2);
+    assert!(!options.to_string().contains("planeCount"));
+    options.plane_count = Some(4);
+    let round_tripped: Options = options.to_string().parse().unwrap();
+    assert_eq!(round_tripped.plane_count, Some(4));
+    assert_eq!(round_tripped.effective_plane_count(), 4);
+    assert!(json!({"planeCount":5})
+        .to_string()
+        .parse::<Options>()
+        .is_err());
+    assert!(json!({"planeCount":0})
+        .to_string()
+        .parse::<Options>()
+        .is_err());
+}
+
+#[test]
+fn color_lerp() {
+    let black = Color { r: 0, g: 0, b: 0 };
+    let orange = Color {
+        r: 255,
+        g: 100,
+        b: 10,
+    };
+    assert_eq!(black.lerp(&orange, 0.0), black);
+    assert_eq!(black.lerp(&orange, 1.0), orange);
+    assert_eq!(
+        black.lerp(&orange, 0.5),
+        Color {
+            r: 128,
+            g: 50,
+            b: 5
+        }
+    );
+    assert_eq!(
+        orange.lerp(&black, 0.5),
+        Color {
+            r: 128,
+            g: 50,
+            b: 5
+        }
+    );
+    assert_eq!(black.lerp(&orange, -1.0), black);
+    assert_eq!(black.lerp(&orange, 2.0), orange);
+}
+
+#[test]
+fn from_8o_header() {
+    let source = "\n# Example game by Someone\n#\n# tickrate 20\n# shiftQuirks: 1\n# vBlankQuirks=false\n# fontStyle schip\n# clipQuirks 7\n\n: main\n  # tickrate 30\n  loop again\n";
+    let options = Options::from_8o_header(source);
+    assert_eq!(options.tickrate, Some(20));
+    assert_eq!(options.quirks.shift, Some(true));
+    assert_eq!(options.quirks.vblank, Some(false));
+    assert_eq!(options.quirks.clip, None);
+    assert_eq!(options.font_style, Font::Schip);
+    assert_eq!(options.max_size, None);
+
+    assert_eq!(Options::from_8o_header(": main\n").tickrate, None);
+}
+
+#[test]
+fn unsupported_by() {
+    let chip8_only = EmulatorCaps::default();
+    let unsupported = Options::new(Platform::XoChip).unsupported_by(&chip8_only);
+    assert!(unsupported.contains(&"XO-CHIP memory"));
+    assert!(!unsupported.contains(&"custom colors"));
+    assert!(Options::new(Platform::Vip)
+        .unsupported_by(&chip8_only)
+        .is_empty());
+
+    let mut options = Options::new(Platform::Vip);
+    options.colors.fill_color = Some(Color { r: 1, g: 2, b: 3 });
+    options.touch_input_mode = TouchMode::Gamepad;
+    assert_eq!(
+        options.unsupported_by(&chip8_only),
+        vec!["custom colors", "touch input"]
+    );
+    let everything = EmulatorCaps {
+        custom_colors: true,
+        high_resolution: true,
+        xo_chip_memory: true,
+        touch_input: true,
+        screen_rotation: true,
+    };
+    assert!(options.unsupported_by(&everything).is_empty());
+}
+
+// Octo writes booleans as 0/1 and numbers as strings in some versions, and colors in any case, so
+// normalize those before comparing.
+fn normalize_octo_value(value: &Value) -> Value {
+    match value {
+        Value::Bool(b) => json!(u8::from(*b)),
+        Value::String(s) if s.parse::<u64>().is_ok() => json!(s.parse::<u64>().unwrap()),
+        Value::String(s) if s.parse::<Color>().is_ok() => json!(s.parse::<Color>().unwrap()),
+        other => other.clone(),
+    }
+}
+
+/// Asserts that Octo's JSON deserializes to `options`, and that serializing `options` writes every
+/// key Octo wrote, with a value Octo would read the same way.
+fn assert_octo_compatible(options: &Options, octo_json: &Value) {
+    let deserialized: Options = octo_json.to_string().parse().unwrap();
+    assert_eq!(&deserialized, options);
+    let serialized = serde_json::to_value(options).unwrap();
+    for (key, value) in octo_json.as_object().unwrap() {
+        let Some(serialized_value) = serialized.get(key) else {
+            panic!("{} is missing from the serialized options", key);
+        };
+        assert_eq!(
+            normalize_octo_value(serialized_value),
+            normalize_octo_value(value),
+            "{} has a different value",
+            key
+        );
+    }
+}
+
+/// Checks every golden file in `tests/golden`, which contain options exported by Octo.
+#[test]
+fn octo_golden_files() {
+    let golden = std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/golden");
+    for entry in std::fs::read_dir(golden).unwrap() {
+        let octo_json: Value = std::fs::read_to_string(entry.unwrap().path())
+            .unwrap()
+            .parse()
+            .unwrap();
+        let options: Options = octo_json.to_string().parse().unwrap();
+        assert_octo_compatible(&options, &octo_json);
+    }
+}
+
+#[test]
+fn octo_golden_new_game() {
+    let octo_json: Value = include_str!("golden/octo_new_game.json").parse().unwrap();
+    let mut options = Options::new(Platform::Vip);
+    options.tickrate = Some(20);
+    options.max_size = Some(3215);
+    options.font_style = Font::Octo;
+    options.start_address = None;
+    options.colors = Colors {
+        fill_color: Some(Color {
+            r: 0xFF,
+            g: 0xCC,
+            b: 0x00,
+        }),
+        fill_color2: Some(Color {
+            r: 0xFF,
+            g: 0x66,
+            b: 0x00,
+        }),
+        blend_color: Some(Color {
+            r: 0x66,
+            g: 0x22,
+            b: 0x00,
+        }),
+        background_color: Some(Color {
+            r: 0x99,
+            g: 0x66,
+            b: 0x00,
+        }),
+        buzz_color: Some(Color {
+            r: 0xFF,
+            g: 0xAA,
+            b: 0x00,
+        }),
+        quiet_color: Some(Color {
+            r: 0x00,
+            g: 0x00,
+            b: 0x00,
+        }),
+        background_transparent: None,
+    };
+    options.quirks.vf_order = Some(false);
+    options.quirks.delay_wrap = None;
+    options.quirks.overflow_i = None;
+    options.quirks.lores_dxy0 = None;
+    assert_octo_compatible(&options, &octo_json);
+}
+
+#[test]
+fn color_dither_density() {
+    let on = Color::new(255, 204, 0);
+    let off = Color::new(0, 0, 64);
+    assert!((on.to_dither_density(&on, &off) - 1.0).abs() < f32::EPSILON);
+    assert!(off.to_dither_density(&on, &off).abs() < f32::EPSILON);
+    let halfway = off.lerp(&on, 0.5);
+    assert!((halfway.to_dither_density(&on, &off) - 0.5).abs() < 0.01);
+    assert_eq!(on.to_dither_density(&off, &off), 0.0);
+}
+
+#[test]
+fn color_functional_notation() {
+    assert_eq!(
+        "rgb(0,128,255)".parse::<Color>().unwrap(),
+        Color {
+            r: 0,
+            g: 128,
+            b: 255
+        }
+    );
+    assert_eq!(
+        "rgb(0, 128, 255)".parse::<Color>().unwrap(),
+        Color {
+            r: 0,
+            g: 128,
+            b: 255
+        }
+    );
+    assert_eq!(
+        "rgba(255,0,0,0.5)".parse::<Color>().unwrap(),
+        Color { r: 255, g: 0, b: 0 }
+    );
+    assert!("rgba(1,2,3)".parse::<Color>().is_err());
+    assert!("rgb(1,2)".parse::<Color>().is_err());
+}
+
+#[test]
+fn normalize_max_size() {
+    let octo_json: Value = include_str!("golden/octo_new_game.json").parse().unwrap();
+    let mut options: Options = octo_json.to_string().parse().unwrap();
+    options.normalize_max_size();
+    assert_eq!(options.max_size, Some(3216));
+    for max_size in [Some(3216), Some(3583), Some(3584), Some(65024), None] {
+        options.max_size = max_size;
+        options.normalize_max_size();
+        assert_eq!(options.max_size, max_size);
+    }
+}
+
+#[test]
+fn buzzer_pitch() {
+    let mut options = Options::default();
+    assert_eq!(options.buzzer_pitch, None);
+    assert!(!options.to_string().contains("buzzerPitch"));
+    options.buzzer_pitch = Some(440);
+    assert!(options.to_string().contains("\"buzzerPitch\":440"));
+    let round_tripped: Options = options.to_string().parse().unwrap();
+    assert_eq!(round_tripped.buzzer_pitch, Some(440));
+    assert!(!options.semantically_eq(&Options::default()));
+}
+
+#[test]
+fn font_address() {
+    let mut options = Options::default();
+    assert_eq!(options.font_address, None);
+    assert_eq!(options.effective_font_address(), 0x50);
+    assert!(!options.to_string().contains("fontAddress"));
+    options.font_address = Some(0);
+    assert!(options.to_string().contains("\"fontAddress\":0"));
+    let round_tripped: Options = options.to_string().parse().unwrap();
+    assert_eq!(round_tripped.font_address, Some(0));
+    assert_eq!(round_tripped.effective_font_address(), 0);
+    assert!(!options.semantically_eq(&Options::default()));
+}
+
+#[test]
+fn colors_planes() {
+    let colors = Colors::default();
+    let planes = colors.as_planes();
+    assert_eq!(planes[0], colors.background_color);
+    assert_eq!(planes[1], colors.fill_color);
+    assert_eq!(planes[2], colors.fill_color2);
+    assert_eq!(planes[3], colors.blend_color);
+    assert_eq!(
+        Colors::from_planes(planes, colors.buzz_color, colors.quiet_color),
+        colors
+    );
+}
+
+#[test]
+fn color_from_integer() {
+    let options: Options = json!({"fillColor":16711680,"blendColor":0})
+        .to_string()
+        .parse()
+        .unwrap();
+    assert_eq!(
+        options.colors.fill_color,
+        Some(Color { r: 255, g: 0, b: 0 })
+    );
+    assert_eq!(options.colors.blend_color, Some(Color { r: 0, g: 0, b: 0 }));
+    assert!(options.to_string().contains("\"fillColor\":\"#FF0000\""));
+    assert!(json!({"fillColor":16777216})
+        .to_string()
+        .parse::<Options>()
+        .is_err());
+    assert!(json!({"fillColor":-1})
+        .to_string()
+        .parse::<Options>()
+        .is_err());
+}
+
+#[test]
+fn without_colors() {
+    let mut options = Options::default();
+    options.without_colors();
+    let serialized = serde_json::to_value(&options).unwrap();
+    for key in serialized.as_object().unwrap().keys() {
+        assert!(
+            !key.ends_with("Color") && !key.ends_with("Color2"),
+            "{}",
+            key
+        );
+    }
+    assert_eq!(options.colors.as_planes(), [None; 4]);
+    assert!(!options.to_ini().contains("colors."));
+}
+
+#[test]
+fn delay_timer_next() {
+    let mut quirks = Quirks::default();
+    assert_eq!(quirks.delay_timer_next(2), 1);
+    assert_eq!(quirks.delay_timer_next(1), 0);
+    assert_eq!(quirks.delay_timer_next(0), 0);
+    quirks.delay_wrap = None;
+    assert_eq!(quirks.delay_timer_next(0), 0);
+    let quirks = Quirks::from(Platform::Dream6800);
+    assert_eq!(quirks.delay_timer_next(1), 0);
+    assert_eq!(quirks.delay_timer_next(0), 255);
+}
+
+#[test]
+fn apply_quirk_str() {
+    let mut options = Options::default();
+    options.apply_quirk_str("clipQuirks=0").unwrap();
+    assert_eq!(options.quirks.clip, Some(ClipMode::WrapBoth));
+    options.apply_quirk_str("shift = 1").unwrap();
+    assert_eq!(options.quirks.shift, Some(true));
+    options.apply_quirk_str("loresDXY0=big_sprite").unwrap();
+    assert_eq!(
+        options.quirks.lores_dxy0,
+        Some(LoResDxy0Behavior::BigSprite)
+    );
+    assert_eq!(options.quirks.res_clear, Some(true));
+    assert_eq!(
+        options.apply_quirk_str("fooQuirks=1"),
+        Err(SetError::UnknownKey("fooQuirks".to_string()))
+    );
+    assert_eq!(
+        options.apply_quirk_str("shift"),
+        Err(SetError::MissingEquals)
+    );
+    assert_eq!(
+        options.apply_quirk_str("shift=maybe"),
+        Err(SetError::InvalidValue("maybe".to_string()))
+    );
+    options.apply_quirk_str("vblankquirks=1").unwrap();
+    assert_eq!(options.quirks.vblank, Some(true));
+    options.apply_quirk_str("shiftQuirks=null").unwrap();
+    assert_eq!(options.quirks.shift, None);
+    assert_eq!(
+        options.apply_quirk_str("tickrate=20"),
+        Err(SetError::UnknownKey("tickrate".to_string()))
+    );
+}
+
+#[test]
+fn apply_env() {
+    std::env::set_var("OCTOPT_TEST_TICKRATE", "42");
+    std::env::set_var("OCTOPT_TEST_QUIRK_SHIFT", "1");
+    std::env::set_var("OCTOPT_TEST_QUIRK_LORES_DXY0", "no_op");
+    std::env::set_var("OCTOPT_TEST_FILL_COLOR", "123456");
+    std::env::set_var("OCTOPT_TEST_UNRELATED", "whatever");
+    let mut options = Options::default();
+    options.apply_env("OCTOPT_TEST").unwrap();
+    assert_eq!(options.tickrate, Some(42));
+    assert_eq!(options.quirks.shift, Some(true));
+    assert_eq!(options.quirks.lores_dxy0, Some(LoResDxy0Behavior::NoOp));
+    assert_eq!(options.colors.fill_color, Some("#123456".parse().unwrap()));
+
+    std::env::set_var("OCTOPT_INVALID_TICKRATE", "fast");
+    assert_eq!(
+        Options::default().apply_env("OCTOPT_INVALID"),
+        Err(SetError::InvalidValue("fast".to_string()))
+    );
+
+    #[cfg(unix)]
+    {
+        use std::ffi::OsStr;
+        use std::os::unix::ffi::OsStrExt;
+        std::env::set_var(
+            OsStr::from_bytes(b"UNRELATED_\xFF"),
+            OsStr::from_bytes(b"\xFF"),
+        );
+        options.apply_env("OCTOPT_TEST").unwrap();
+        std::env::set_var("OCTOPT_BYTES_TICKRATE", OsStr::from_bytes(b"4\xFF"));
+        assert_eq!(
+            Options::default().apply_env("OCTOPT_BYTES"),
+            Err(SetError::InvalidValue("4\u{FFFD}".to_string()))
+        );
+    }
+}
+
+#[test]
+fn render_glyph_ascii() {
+    assert_eq!(
+        Font::Octo.render_glyph_ascii(0, false).unwrap(),
+        "####\n#  #\n#  #\n#  #\n####"
+    );
+    assert_eq!(
+        Font::Octo.render_glyph_ascii(1, false).unwrap(),
+        "  # \n ## \n  # \n  # \n ###"
+    );
+    assert_eq!(
+        Font::Octo
+            .render_glyph_ascii(0, true)
+            .unwrap()
+            .lines()
+            .count(),
+        10
+    );
+    assert_eq!(Font::Schip.render_glyph_ascii(0xA, true), None);
+    assert_eq!(Font::Vip.render_glyph_ascii(0, true), None);
+    assert_eq!(Font::Octo.render_glyph_ascii(0x10, false), None);
+}
+
+#[test]
+fn ini_key_for() {
+    assert_eq!(
+        Options::ini_key_for(OptionsField::Tickrate),
+        Some("core.tickrate")
+    );
+    assert_eq!(
+        Options::ini_key_for(OptionsField::BackgroundColor),
+        Some("colors.plane0")
+    );
+    assert_eq!(Options::ini_key_for(OptionsField::PlaneCount), None);
+    let ini = Options::new(Platform::XoChip).to_ini();
+    for field in [
+        OptionsField::MaxSize,
+        OptionsField::QuietColor,
+        OptionsField::LoresDxy0,
+        OptionsField::OverflowI,
+    ] {
+        let key = Options::ini_key_for(field).unwrap();
+        assert!(ini.contains(&format!("{}=", key)), "{}", key);
+    }
+}
+
+#[test]
+fn color_from_str_quoted() {
+    let color = Color {
+        r: 0xFF,
+        g: 0xCC,
+        b: 0x00,
+    };
+    assert_eq!(" #FFCC00 ".parse::<Color>().unwrap(), color);
+    assert_eq!("\"FFCC00\"".parse::<Color>().unwrap(), color);
+    assert_eq!("' #FFCC00 '".parse::<Color>().unwrap(), color);
+    assert!("\"FFCC00'".parse::<Color>().is_err());
+    let options = Options::from_ini("colors.plane1 = \"FFCC00\"\n").unwrap();
+    assert_eq!(options.colors.fill_color, Some(color));
+}
+
+#[test]
+fn quirks_octo_subset() {
+    let quirks = Quirks::from(Platform::XoChip).octo_subset();
+    assert_eq!(quirks.overflow_i, None);
+    assert_eq!(quirks.lores_dxy0, None);
+    assert_eq!(quirks.shift, Quirks::from(Platform::XoChip).shift);
+    let mut options = Options::default();
+    options.quirks = quirks;
+    let keys: Vec<String> = serde_json::to_value(&options)
+        .unwrap()
+        .as_object()
+        .unwrap()
+        .keys()
+        .filter(|key| key.ends_with("Quirks"))
+        .cloned()
+        .collect();
+    assert_eq!(keys.len(), 7);
+}
+
+#[test]
+fn resolved_quirks_serde() {
+    let mut quirks = Quirks::from(Platform::Vip);
+    quirks.scroll = None;
+    let resolved = quirks.resolve();
+    assert!(resolved.vblank);
+    assert!(!resolved.scroll);
+    let json = serde_json::to_value(resolved).unwrap();
+    assert_eq!(json["shift"], json!(false));
+    assert_eq!(json["loresDxy0"], json!("no_op"));
+    assert!(json
+        .as_object()
+        .unwrap()
+        .values()
+        .all(|value| value.is_boolean() || value.is_string()));
+    assert_eq!(
+        serde_json::from_value::<ResolvedQuirks>(json).unwrap(),
+        resolved
+    );
+}
+
+#[test]
+fn font_layout() {
+    let mut layout = FontLayout {
+        small: Font::Octo,
+        big: Some(Font::Fish),
+        small_addr: 0,
+        big_addr: 0x50,
+    };
+    assert_eq!(layout.memory_image().unwrap().len(), 80 + 160);
+    layout.big_addr = 0x40;
+    assert_eq!(layout.memory_image(), Err(LayoutError::Overlap));
+    layout.big_addr = 0x1C0;
+    assert_eq!(layout.memory_image(), Err(LayoutError::OutOfBounds));
+    layout.big = Some(Font::Vip);
+    assert_eq!(layout.memory_image(), Err(LayoutError::MissingBigDigits));
+    layout.big = None;
+    assert_eq!(layout.memory_image().unwrap().len(), 80);
+}
+
+#[test]
+fn color_readable_on() {
+    let black = Color { r: 0, g: 0, b: 0 };
+    let white = Color {
+        r: 255,
+        g: 255,
+        b: 255,
+    };
+    let near_white = Color {
+        r: 240,
+        g: 240,
+        b: 230,
+    };
+    let near_black = Color {
+        r: 20,
+        g: 10,
+        b: 30,
+    };
+    assert_eq!(near_white.readable_on(), black);
+    assert_eq!(near_black.readable_on(), white);
+}
+
+#[test]
+fn quirk_key_casing() {
+    for key in ["vBlankQuirks", "vblankQuirks", "vblankquirks"] {
+        let options: Options = serde_json::from_value(json!({ key: 1 })).unwrap();
+        assert_eq!(options.quirks.vblank, Some(true), "{}", key);
+        let serialized = serde_json::to_value(&options).unwrap();
+        assert_eq!(serialized["vBlankQuirks"], json!(true));
+    }
+    let options: Options = serde_json::from_str(r#"{"loresdxy0quirks": "no_op"}"#).unwrap();
+    assert_eq!(options.quirks.lores_dxy0, Some(LoResDxy0Behavior::NoOp));
+}
+
+#[test]
+fn from_json_reader_limited() {
+    let json = r#"{"tickrate": 30, "clipQuirks": 1}"#;
+    let options = Options::from_json_reader_limited(json.as_bytes(), json.len()).unwrap();
+    assert_eq!(options.tickrate, Some(30));
+    assert!(matches!(
+        Options::from_json_reader_limited(json.as_bytes(), json.len() - 1),
+        Err(ParseError::TooLarge(_))
+    ));
+    assert!(matches!(
+        Options::from_json_reader_limited(&b"{"[..], 100),
+        Err(ParseError::Json(_))
+    ));
+}
+
+#[test]
+fn clip_mode() {
+    for (value, mode) in [
+        (json!(1), ClipMode::ClipBoth),
+        (json!(true), ClipMode::ClipBoth),
+        (json!(0), ClipMode::WrapBoth),
+        (json!(false), ClipMode::WrapBoth),
+        (json!("clip_x_wrap_y"), ClipMode::ClipXWrapY),
+        (json!("wrap_x_clip_y"), ClipMode::WrapXClipY),
+    ] {
+        let options: Options = serde_json::from_value(json!({ "clipQuirks": value })).unwrap();
+        assert_eq!(options.quirks.clip, Some(mode));
+    }
+    assert!(serde_json::from_value::<Options>(json!({ "clipQuirks": 2 })).is_err());
+
+    let mut options = Options::default();
+    options.quirks.clip = Some(ClipMode::ClipBoth);
+    assert_eq!(
+        serde_json::to_value(&options).unwrap()["clipQuirks"],
+        json!(true)
+    );
+    options.quirks.clip = Some(ClipMode::ClipXWrapY);
+    assert_eq!(
+        serde_json::to_value(&options).unwrap()["clipQuirks"],
+        json!("clip_x_wrap_y")
+    );
+    let ini = options.to_ini();
+    assert!(ini.contains("quirks.clip=clip_x_wrap_y"));
+    assert_eq!(
+        Options::from_ini(&ini).unwrap().quirks.clip,
+        Some(ClipMode::ClipXWrapY)
+    );
+}
+
+#[test]
+fn colors_into_iter() {
+    let colors = Colors {
+        buzz_color: None,
+        ..Colors::default()
+    };
+    let entries: Vec<(ColorRole, Option<Color>)> = colors.clone().into_iter().collect();
+    assert_eq!(
+        entries.iter().map(|(role, _)| *role).collect::<Vec<_>>(),
+        [
+            ColorRole::Fill,
+            ColorRole::Fill2,
+            ColorRole::Blend,
+            ColorRole::Background,
+            ColorRole::Buzz,
+            ColorRole::Quiet,
+        ]
+    );
+    assert_eq!(entries[0].1, colors.fill_color);
+    assert_eq!(entries[4].1, None);
+}
+
+#[test]
+fn quirks_to_json_octo_minimal() {
+    assert_eq!(Quirks::default().to_json_octo_minimal(), json!({}));
+    let mut quirks = Quirks::default();
+    quirks.shift = Some(true);
+    quirks.scroll = None;
+    assert_eq!(
+        quirks.to_json_octo_minimal(),
+        json!({ "shiftQuirks": true })
+    );
+}
+
+#[test]
+fn clamp_tickrate_for() {
+    let mut options = Options::default();
+    options.tickrate = Some(10000);
+    options.clamp_tickrate_for(Platform::Vip);
+    assert_eq!(options.tickrate, Some(30));
+    options.tickrate = Some(0);
+    options.clamp_tickrate_for(Platform::XoChip);
+    assert_eq!(options.tickrate, Some(1));
+    options.tickrate = Some(500);
+    options.clamp_tickrate_for(Platform::Octo);
+    assert_eq!(options.tickrate, Some(500));
+    options.tickrate = None;
+    options.clamp_tickrate_for(Platform::Schip);
+    assert_eq!(options.tickrate, None);
+}
+
+#[test]
+fn is_octo_new_game_default() {
+    let mut options: Options = include_str!("golden/octo_new_game.json").parse().unwrap();
+    assert!(options.is_octo_new_game_default());
+    assert!(!Options::default().is_octo_new_game_default());
+    options.tickrate = Some(30);
+    assert!(!options.is_octo_new_game_default());
+}
+
+#[test]
+fn color_new() {
+    let color = Color::new(1, 2, 3);
+    assert_eq!(color.r, 1);
+    assert_eq!(color.g, 2);
+    assert_eq!(color.b, 3);
+    assert_eq!(color.rgb(), (1, 2, 3));
+    assert_eq!(color, "#010203".parse().unwrap());
+}
+
+#[test]
+fn background_transparent() {
+    let mut options = Options::default();
+    assert!(serde_json::to_value(&options)
+        .unwrap()
+        .get("backgroundTransparent")
+        .is_none());
+    let opaque = options.clone();
+    options.colors.background_transparent = Some(false);
+    assert!(options.semantically_eq(&opaque));
+    options.colors.background_transparent = Some(true);
+    assert!(!options.semantically_eq(&opaque));
+    let serialized = serde_json::to_value(&options).unwrap();
+    assert_eq!(serialized["backgroundTransparent"], json!(true));
+    let deserialized: Options = serde_json::from_value(serialized).unwrap();
+    assert_eq!(deserialized.colors.background_transparent, Some(true));
+}
+
+#[test]
+fn try_from_cart_bytes() {
+    let options = Options::new(Platform::Schip);
+    let json = serde_json::to_string(&options).unwrap();
+    let cart_json = json!({ "program": ": main\n  loop again\n", "options": options });
+    let ini = options.clone().to_ini();
+    let from_json = Options::try_from_cart_bytes(json.as_bytes()).unwrap();
+    let from_cart_json = Options::try_from_cart_bytes(cart_json.to_string().as_bytes()).unwrap();
+    let from_ini = Options::try_from_cart_bytes(ini.as_bytes()).unwrap();
+    assert_eq!(from_json, options);
+    assert_eq!(from_cart_json, options);
+    assert!(from_ini.semantically_eq(&options));
+    assert!(matches!(
+        Options::try_from_cart_bytes(b"GIF89a\x80\x00\x40\x00"),
+        Err(ParseError::UnsupportedFormat)
+    ));
+
+    let cart = octocart(cart_json.to_string().as_bytes());
+    assert_eq!(Options::try_from_cart_bytes(&cart).unwrap(), options);
+
+    // A few bytes that claim huge frames, each with only a clear code and an end code, aren't
+    // decoded into that many pixels
+    let mut huge = b"GIF89a\xFF\xFF\xFF\xFF\x00\x00\x00".to_vec();
+    huge.extend(b"\x2C\x00\x00\x00\x00\xA0\x0F\xA0\x0F\x00\x02\x01\x2C\x00");
+    for _ in 0..4 {
+        huge.extend(b"\x2C\x00\x00\x00\x00\xFF\xFF\xFF\xFF\x00\x02\x01\x2C\x00");
+    }
+    huge.push(0x3B);
+    assert!(matches!(
+        Options::try_from_cart_bytes(&huge),
+        Err(ParseError::UnsupportedFormat)
+    ));
+}
+
+/// Builds an Octocart like Octo does, with the payload stored two bits to a pixel on top of a
+/// label, split over two frames.
+fn octocart(payload: &[u8]) -> Vec<u8> {
+    let mut data = (payload.len() as u32).to_be_bytes().to_vec();
+    data.extend(payload);
+    let mut pixels: Vec<u8> = data
+        .iter()
+        .flat_map(|byte| [6, 4, 2, 0].map(|shift| byte >> shift & 3))
+        .collect();
+    let width = 64;
+    let height = (pixels.len() / width / 2 + 1) * 2;
+    pixels.resize(width * height, 0);
+    for (index, pixel) in pixels.iter_mut().enumerate() {
+        *pixel |= ((index % 4) as u8) << 2;
+    }
+
+    let mut gif = b"GIF89a".to_vec();
+    gif.extend((width as u16).to_le_bytes());
+    gif.extend((height as u16).to_le_bytes());
+    // A global color table with 16 colors
+    gif.extend([0x83, 0, 0]);
+    gif.extend([0; 3 * 16]);
+    for frame in pixels.chunks(width * height / 2) {
+        // The delay before the frame
+        gif.extend([0x21, 0xF9, 4, 0, 10, 0, 0, 0]);
+        gif.extend([0x2C, 0, 0, 0, 0]);
+        gif.extend((width as u16).to_le_bytes());
+        gif.extend((height as u16 / 2).to_le_bytes());
+        gif.extend([0, 4]);
+        for block in lzw_encode(frame, 4).chunks(255) {
+            gif.push(block.len() as u8);
+            gif.extend(block);
+        }
+        gif.push(0);
+    }
+    gif.push(0x3B);
+    gif
+}
+
+/// Compresses the color indices of a GIF frame with LZW.
+fn lzw_encode(pixels: &[u8], min_code_size: u8) -> Vec<u8> {
+    let clear = 1u16 << min_code_size;
+    let mut codes = std::collections::HashMap::new();
+    let mut next = clear + 2;
+    let mut code_size = min_code_size + 1;
+    let mut output = vec![(clear, code_size)];
+    let mut string = u16::from(pixels[0]);
+    for &pixel in &pixels[1..] {
+        if let Some(&code) = codes.get(&(string, pixel)) {
+            string = code;
+            continue;
+        }
+        output.push((string, code_size));
+        codes.insert((string, pixel), next);
+        next += 1;
+        // The decoder adds each code one code later, so the code size grows a code later too
+        if next - 1 == 1 << code_size {
+            code_size += 1;
+        }
+        if next == 4095 {
+            output.push((clear, code_size));
+            codes.clear();
+            next = clear + 2;
+            code_size = min_code_size + 1;
+        }
+        string = u16::from(pixel);
+    }
+    output.push((string, code_size));
+    // The decoder adds a code for the last string too, before it reads the end code
+    if next == 1 << code_size {
+        code_size += 1;
+    }
+    output.push((clear + 1, code_size));
+
+    let mut bytes = Vec::new();
+    let (mut bits, mut bit_count) = (0u32, 0);
+    for (code, code_size) in output {
+        bits |= u32::from(code) << bit_count;
+        bit_count += code_size;
+        while bit_count >= 8 {
+            bytes.push(bits as u8);
+            bits >>= 8;
+            bit_count -= 8;
+        }
+    }
+    if bit_count > 0 {
+        bytes.push(bits as u8);
+    }
+    bytes
+}
+
+#[test]
+fn quirk_names_output_mode() {
+    let mut quirks = Quirks::default();
+    quirks.shift = Some(true);
+    let serialized = serde_json::to_value(&quirks).unwrap();
+    assert_eq!(serialized["shiftQuirks"], json!(true));
+    let short: Value = quirks.to_json_short_names().parse().unwrap();
+    assert_eq!(short["shift"], json!(true));
+    assert!(short.get("shiftQuirks").is_none());
+
+    let options = Options::default();
+    let short = options.to_json_short_quirk_names();
+    let short_json: Value = short.parse().unwrap();
+    assert_eq!(short_json["quirks"]["shift"], json!(false));
+    assert!(short_json.get("shiftQuirks").is_none());
+    assert_eq!(short.parse::<Options>().unwrap(), options);
+
+    for json in [
+        json!({ "shiftQuirks": 1 }),
+        json!({ "quirks": { "shift": 1 } }),
+    ] {
+        let options: Options = serde_json::from_value(json).unwrap();
+        assert_eq!(options.quirks.shift, Some(true));
+    }
+    // At the top level, the short names aren't quirks, so other uses of them are fine
+    let options: Options = serde_json::from_value(json!({ "shift": "left", "scroll": 4 })).unwrap();
+    assert_eq!(options.quirks.shift, None);
+    let mut raw: RawOptions = r#"{"shift":"left","shiftQuirks":1}"#.parse().unwrap();
+    raw.set_options(options);
+    assert_eq!(raw.to_string(), r#"{"shift":"left"}"#);
+}
+
+#[test]
+fn display_dimensions() {
+    let mut options = Options::new(Platform::XoChip);
+    assert_eq!(options.display_dimensions(), (128, 64));
+    options.screen_rotation = ScreenRotation::ClockWise;
+    assert_eq!(options.display_dimensions(), (64, 128));
+    let mut options = Options::new(Platform::Vip);
+    options.screen_rotation = ScreenRotation::UpsideDown;
+    assert_eq!(options.display_dimensions(), (64, 32));
+}
+
+#[test]
+fn write_ndjson() {
+    let configs = vec![
+        ("octo".to_string(), Options::new(Platform::Octo)),
+        ("vip".to_string(), Options::new(Platform::Vip)),
+        ("schip".to_string(), Options::new(Platform::Schip)),
+    ];
+    let mut output = Vec::new();
+    Options::write_ndjson(&configs, &mut output).unwrap();
+    let output = String::from_utf8(output).unwrap();
+    assert_eq!(output.lines().count(), configs.len());
+    for (line, (key, options)) in output.lines().zip(&configs) {
+        let value: Value = serde_json::from_str(line).unwrap();
+        assert_eq!(value["key"], json!(key));
+        let parsed: Options = serde_json::from_value(value["options"].clone()).unwrap();
+        assert_eq!(&parsed, options);
+    }
+}
+
+#[test]
+fn jump_register() {
+    let mut quirks = Quirks::default();
+    quirks.jump0 = Some(true);
+    assert_eq!(quirks.jump_register(0x2A0), 2);
+    assert_eq!(quirks.jump_register(0xF00), 0xF);
+    quirks.jump0 = Some(false);
+    assert_eq!(quirks.jump_register(0x2A0), 0);
+    quirks.jump0 = None;
+    assert_eq!(quirks.jump_register(0x2A0), 0);
+}
+
+#[test]
+fn quirks_markdown_table() {
+    let quirks = Quirks::from(Platform::Schip);
+    assert_eq!(quirks.capability_row().len(), 11);
+    let table = quirks.markdown_table();
+    let lines: Vec<&str> = table.lines().collect();
+    assert_eq!(lines[0], "| Quirk | Value | Description |");
+    assert_eq!(lines[1], "|---|---|---|");
+    assert_eq!(lines.len(), 2 + 14);
+    assert!(lines[6].starts_with("| clipQuirks | clip_both |"));
+    assert!(lines[9].starts_with("| loresDXY0Quirks |"));
+    assert!(lines
+        .iter()
+        .skip(2)
+        .all(|line| line.matches('|').count() == 4));
+}
+
+#[test]
+fn tickrate_from_float() {
+    let options: Options = r#"{"tickrate": 20.0}"#.parse().unwrap();
+    assert_eq!(options.tickrate, Some(20));
+    let options: Options = r#"{"tickrate": 20.5}"#.parse().unwrap();
+    assert_eq!(options.tickrate, Some(21));
+    let options: Options = r#"{"tickrate": 20.4}"#.parse().unwrap();
+    assert_eq!(options.tickrate, Some(20));
+    assert!(r#"{"tickrate": 70000.0}"#.parse::<Options>().is_err());
+    assert!(r#"{"tickrate": -1.5}"#.parse::<Options>().is_err());
+    let options: Options = r#"{"buzzerPitch": 440.5}"#.parse().unwrap();
+    assert_eq!(options.buzzer_pitch, Some(441));
+}
+
+#[test]
+fn colors_semantically_eq() {
+    let mut options = Options::default();
+    options.without_colors();
+    let unset = options.colors;
+    assert_ne!(unset, Colors::default());
+    assert!(unset.semantically_eq(&Colors::default()));
+    let changed = Colors {
+        quiet_color: Some(Color::new(1, 2, 3)),
+        ..Colors::default()
+    };
+    assert!(!unset.semantically_eq(&changed));
+}
+
+#[test]
+fn parse_error_source() {
+    use std::error::Error;
+
+    let error = "{\"tickrate\": true}".parse::<Options>().unwrap_err();
+    assert!(matches!(error, ParseError::Json(_)));
+    let source = error.source().unwrap();
+    assert!(source.downcast_ref::<serde_json::Error>().is_some());
+    assert!(ParseError::UnsupportedFormat.source().is_none());
+}
+
+#[test]
+fn normalize() {
+    let mut first: Options = serde_json::from_value(json!({
+        "maxSize": 3215,
+        "fillColor": "#ffcc00",
+        "shiftQuirks": 1,
+        "tickrate": "20",
+        "fontStyle": "schip",
+        "fontBigStyle": "schip"
+    }))
+    .unwrap();
+    let mut second: Options = serde_json::from_value(json!({
+        "tickrate": 20.0,
+        "shiftQuirks": true,
+        "fillColor": "rgb(255, 204, 0)",
+        "maxSize": 3216,
+        "fontStyle": "schip"
+    }))
+    .unwrap();
+    assert_ne!(first, second);
+    first.normalize();
+    second.normalize();
+    assert_eq!(
+        serde_json::to_string(&first).unwrap(),
+        serde_json::to_string(&second).unwrap()
+    );
+    assert_eq!(first.max_size, Some(3216));
+}
+
+#[test]
+fn from_core_option_string() {
+    let options = Options::from_core_option_string(
+        "tickrate=30; font=schip;shift=enabled;vblank=disabled;clipQuirks=1;octo_frontend_thing=yes;",
+    )
+    .unwrap();
+    assert_eq!(options.tickrate, Some(30));
+    assert_eq!(options.font_style, Font::Schip);
+    assert_eq!(options.quirks.shift, Some(true));
+    assert_eq!(options.quirks.vblank, Some(false));
+    assert_eq!(options.quirks.clip, Some(ClipMode::ClipBoth));
+    assert_eq!(options.max_size, Options::default().max_size);
+    assert_eq!(
+        Options::from_core_option_string("tickrate=fast"),
+        Err(SetError::InvalidValue("fast".to_string()))
+    );
+
+    let options =
+        Options::from_core_option_string("max_rom=4096;buzzer=FF0000;startAddress=0x600").unwrap();
+    assert_eq!(options.max_size, Some(4096));
+    assert_eq!(options.colors.buzz_color, Some(Color::new(255, 0, 0)));
+    assert_eq!(options.start_address, Some(0x600));
+    assert_eq!(
+        Options::from_core_option_string("planeCount=9"),
+        Err(SetError::InvalidValue("9".to_string()))
+    );
+    let options = Options::from_core_option_string("tickrate=null").unwrap();
+    assert_eq!(options.tickrate, None);
+}
+
+#[test]
+fn options_field_from_json_key() {
+    assert_eq!(
+        OptionsField::from_json_key("tickrate"),
+        Some(OptionsField::Tickrate)
+    );
+    assert_eq!(
+        OptionsField::from_json_key("max_rom"),
+        Some(OptionsField::MaxSize)
+    );
+    assert_eq!(
+        OptionsField::from_json_key("silenceColor"),
+        Some(OptionsField::QuietColor)
+    );
+    for key in ["shiftQuirks", "shiftquirks", "shift"] {
+        assert_eq!(OptionsField::from_json_key(key), Some(OptionsField::Shift));
+    }
+    for key in ["loresDXY0Quirks", "loresDxy0", "loresDXY0"] {
+        assert_eq!(
+            OptionsField::from_json_key(key),
+            Some(OptionsField::LoresDxy0)
+        );
+    }
+    assert_eq!(
+        OptionsField::from_json_key("jump"),
+        Some(OptionsField::Jump0)
+    );
+    assert_eq!(OptionsField::from_json_key("title"), None);
+}
+
+#[test]
+fn font_get_font() {
+    let fish = Font::Fish.get_font();
+    assert_eq!(fish.big_glyph_height, 9);
+    assert_eq!(fish.big_glyph_range, Some(0..=0xF));
+    let schip = Font::Schip.get_font();
+    assert_eq!(schip.big_glyph_range, Some(0..=9));
+    assert_eq!(schip.small, Font::Schip.get_font_data().0);
+    let vip = Font::Vip.get_font();
+    assert_eq!(vip.big, None);
+    assert_eq!(vip.big_glyph_height, 0);
+    assert_eq!(vip.big_glyph_range, None);
+}
+
+#[test]
+fn screen_rotation_scope() {
+    assert_eq!(RotationScope::default(), RotationScope::Display);
+    for rotation in [
+        ScreenRotation::Normal,
+        ScreenRotation::ClockWise,
+        ScreenRotation::UpsideDown,
+        ScreenRotation::CounterClockWise,
+    ] {
+        assert_eq!(rotation.scope(), RotationScope::Display);
+        assert!(!rotation.affects_drawing());
+    }
+}
+
+#[test]
+fn colors_palette_string_round_trip() {
+    let palette = "#111111,#222222,#333333,#444444,#555555,#666666";
+    let colors = Colors::from_palette_string(palette).unwrap();
+    assert_eq!(colors.background_color, Some(Color::new(0x11, 0x11, 0x11)));
+    assert_eq!(colors.quiet_color, Some(Color::new(0x66, 0x66, 0x66)));
+    assert_eq!(colors.to_palette_string(), palette);
+    assert_eq!(
+        Colors::from_palette_string(&palette.replace(',', "|")).unwrap(),
+        colors
+    );
+    assert!(Colors::from_palette_string("#111111,#222222").is_err());
+}
+
+#[test]
+fn tickrate_instructions_per_second() {
+    let mut options = Options::default();
+    options.tickrate = Some(20);
+    assert_eq!(options.instructions_per_second(), Some(1200));
+    options.set_tickrate_from_ips(1200);
+    assert_eq!(options.tickrate, Some(20));
+    options.set_tickrate_from_ips(1230);
+    assert_eq!(options.tickrate, Some(21));
+    options.tickrate = None;
+    assert_eq!(options.instructions_per_second(), None);
+}
+
+#[test]
+fn instructions_per_ms() {
+    let mut options = Options::default();
+    options.tickrate = Some(500);
+    assert_eq!(options.instructions_per_ms(), Some(30.0));
+    options.tickrate = Some(15);
+    assert!((options.instructions_per_ms().unwrap() - 0.9).abs() < f32::EPSILON);
+    options.tickrate = None;
+    assert_eq!(options.instructions_per_ms(), None);
+}
+
+#[test]
+fn lint() {
+    assert_eq!(Options::default().lint(), vec![]);
+    for platform in [
+        Platform::Vip,
+        Platform::Eti660,
+        Platform::Schip,
+        Platform::XoChip,
+    ] {
+        assert_eq!(Options::new(platform).lint(), vec![]);
+    }
+
+    let mut options = Options::default();
+    options.tickrate = Some(0);
+    options.start_address = Some(0x1200);
+    options.plane_count = Some(1);
+    options.colors.blend_color = Some(Color::new(1, 2, 3));
+    options.quirks.clip_collision = Some(true);
+    options.font_style = Font::Vip;
+    let mut eti_660 = Options::new(Platform::Eti660);
+    eti_660.max_size = Some(3584);
+    assert_eq!(eti_660.lint(), vec![Lint::ImpossibleAddress]);
+
+    let lints = options.lint();
+    assert_eq!(
+        lints,
+        vec![
+            Lint::ClipCollisionWithoutClipping,
+            Lint::MissingBigFont,
+            Lint::UnusedColors,
+            Lint::ImpossibleAddress,
+            Lint::ZeroTickrate,
+        ]
+    );
+    assert_eq!(
+        lints.iter().map(Lint::severity).max(),
+        Some(Severity::Error)
+    );
+}
+
+#[test]
+fn flat_map_round_trip() {
+    for platform in [Platform::Octo, Platform::Schip, Platform::XoChip] {
+        let mut options = Options::new(platform);
+        options.colors.background_transparent = Some(true);
+        let flat = options.to_flat_map();
+        assert_eq!(Options::from_flat_map(&flat).unwrap(), options);
+    }
+    let mut flat = std::collections::BTreeMap::new();
+    flat.insert("fontStyle".to_string(), "comicsans".to_string());
+    assert!(Options::from_flat_map(&flat).is_err());
+}
+
+#[test]
+fn font_glyph_diff() {
+    assert_eq!(Font::glyph_diff(Font::Octo, Font::Schip), Vec::<u8>::new());
+    assert_ne!(Font::Octo.get_font_data().1, Font::Schip.get_font_data().1);
+    let diff = Font::glyph_diff(Font::Octo, Font::Vip);
+    assert!(!diff.is_empty());
+    assert!(diff.iter().all(|&digit| digit < 16));
+}
+
+#[test]
+fn quirks_or_accessors() {
+    let mut quirks = Quirks::default();
+    assert!(!quirks.shift_or(true));
+    quirks.shift = None;
+    assert!(quirks.shift_or(true));
+    assert!(!quirks.shift_or(false));
+    quirks.clip = None;
+    assert_eq!(quirks.clip_or(ClipMode::ClipBoth), ClipMode::ClipBoth);
+    assert!(quirks.res_clear_or(false));
+}
+
+#[test]
+fn hexadecimal_strings() {
+    let options: Options = json!({"startAddress":"0x200","maxSize":"0XE00","tickrate":"20"})
+        .to_string()
+        .parse()
+        .unwrap();
+    assert_eq!(options.start_address, Some(512));
+    assert_eq!(options.max_size, Some(3584));
+    assert_eq!(options.tickrate, Some(20));
+}
+
+#[test]
+fn quirks_builder() {
+    let vip = Quirks::from(Platform::Vip);
+    let mut quirks = QuirksBuilder::from_platform(Platform::Vip)
+        .with_shift(!vip.shift.unwrap())
+        .build();
+    assert_eq!(quirks.shift, Some(!vip.shift.unwrap()));
+    quirks.shift = vip.shift;
+    assert_eq!(quirks, vip);
+}
+
+#[test]
+fn quirks_interaction_notes() {
+    let mut quirks = Quirks::default();
+    assert!(quirks.interaction_notes().is_empty());
+    quirks.scroll = Some(true);
+    quirks.res_clear = Some(false);
+    let notes = quirks.interaction_notes();
+    assert_eq!(notes.len(), 1);
+    assert!(notes[0].contains("scroll"));
+    quirks.res_clear = Some(true);
+    assert!(quirks.interaction_notes().is_empty());
+}
+
+#[test]
+fn quirks_bitfield() {
+    let mut quirks = Quirks::from(Platform::Schip);
+    quirks.shift = None;
+    quirks.load_store = Some(LoadStoreMode::IncrementByXPlus1);
+    quirks.lores_dxy0 = Some(LoResDxy0Behavior::BigSprite);
+    let bits = quirks.to_bitfield();
+    let unpacked = Quirks::from_bitfield(bits).unwrap();
+    assert_eq!(unpacked, quirks);
+    assert_eq!(unpacked.shift, None);
+    assert_eq!(unpacked.load_store, Some(LoadStoreMode::IncrementByXPlus1));
+
+    quirks.lores_dxy0 = None;
+    assert_ne!(quirks.to_bitfield(), bits);
+    assert_eq!(Quirks::from_bitfield(quirks.to_bitfield()), Some(quirks));
+    assert_eq!(Quirks::from_bitfield(0b11), None);
+    assert_eq!(Quirks::from_bitfield(1 << 31), None);
+}
+
+#[test]
+fn project_from_octo_json() {
+    let source = ": main\n\tloop again\n";
+    let json = json!({
+        "key": "abc123",
+        "program": source,
+        "options": {"tickrate": 20, "fillColor": "#FFCC00", "shiftQuirks": true}
+    });
+    let project = Project::from_octo_json(&json.to_string()).unwrap();
+    assert_eq!(project.source, source);
+    assert_eq!(project.label, "");
+    assert_eq!(project.options.tickrate, Some(20));
+    assert_eq!(project.options.quirks.shift, Some(true));
+    assert!(Project::from_octo_json(r#"{"options":{}}"#).is_err());
+}
+
+#[test]
+fn colors_customized_fields() {
+    assert_eq!(Colors::default().customized_fields(), vec![]);
+    let colors = Colors {
+        fill_color: Some(Color::new(0, 0, 255)),
+        quiet_color: None,
+        ..Colors::default()
+    };
+    assert_eq!(colors.customized_fields(), vec![ColorRole::Fill]);
+}
+
+#[test]
+fn quirks_logic_vf_result() {
+    let mut quirks = Quirks::default();
+    quirks.logic = Some(false);
+    assert_eq!(quirks.logic_vf_result(1), None);
+    quirks.logic = Some(true);
+    assert_eq!(quirks.logic_vf_result(1), Some(0));
+    quirks.logic = None;
+    assert_eq!(quirks.logic_vf_result(1), None);
+}
+
+#[test]
+fn raw_options_round_trip() {
+    let json = r#"{"title":"My game","tickrate":"20","shiftQuirks":1,"loadStoreQuirks":0,"max_rom":3584,"fillColor":"FFCC00"}"#;
+    let mut raw: RawOptions = json.parse().unwrap();
+    assert_eq!(raw.to_string(), json);
+    let options = raw.options().unwrap();
+    assert_eq!(options.quirks.shift, Some(true));
+
+    raw.set_options(options.clone());
+    assert_eq!(raw.to_string(), json);
+
+    let mut options = options;
+    options.tickrate = Some(30);
+    options.quirks.shift = Some(false);
+    options.quirks.load_store = None;
+    options.touch_input_mode = TouchMode::Swipe;
+    raw.set_options(options.clone());
+    assert_eq!(
+        raw.to_string(),
+        r#"{"title":"My game","tickrate":"30","shiftQuirks":0,"max_rom":3584,"fillColor":"FFCC00","touchInputMode":"swipe"}"#
+    );
+    assert_eq!(raw.options().unwrap(), options);
+
+    let mut raw: RawOptions = r##"{"buzzer":"#FF0000","silence":"#000000"}"##
+        .parse()
+        .unwrap();
+    let mut options = raw.options().unwrap();
+    options.colors.buzz_color = Some(Color::new(0, 0xFF, 0));
+    raw.set_options(options.clone());
+    assert_eq!(
+        raw.to_string(),
+        r##"{"buzzer":"#00FF00","silence":"#000000"}"##
+    );
+    assert_eq!(raw.options().unwrap(), options);
+
+    let json =
+        r##"{"quirks":{"clip":true,"shift":1},"colors":{"buzz":"#FF0000","fill":"#FFCC00"}}"##;
+    let mut raw: RawOptions = json.parse().unwrap();
+    let mut options = raw.options().unwrap();
+    raw.set_options(options.clone());
+    assert_eq!(raw.to_string(), json);
+    options.quirks.shift = Some(false);
+    options.quirks.clip = None;
+    options.colors.fill_color = Some(Color::new(0xFF, 0xFF, 0xFF));
+    raw.set_options(options.clone());
+    assert_eq!(
+        raw.to_string(),
+        r##"{"quirks":{"shift":0},"colors":{"buzz":"#FF0000","fill":"#FFFFFF"}}"##
+    );
+    assert_eq!(raw.options().unwrap(), options);
+}
+
+#[test]
+fn colors_invert() {
+    let mut colors = Colors::default();
+    colors.invert();
+    assert_eq!(colors.background_color, Some(Color::new(255, 255, 255)));
+    assert_eq!(colors.blend_color, Some(Color::new(0, 255, 255)));
+    colors.invert();
+    assert_eq!(colors, Colors::default());
+}
+
+#[test]
+fn js_object_map() {
+    let options = Options::new(Platform::Schip);
+    let map = options.to_js_object_map();
+    assert_eq!(map["tickrate"], json!(options.tickrate));
+    assert_eq!(map["shiftQuirks"], json!(options.quirks.shift));
+    let serialized = serde_json::to_value(&options).unwrap();
+    let keys: Vec<&String> = serialized.as_object().unwrap().keys().collect();
+    assert_eq!(map.keys().collect::<Vec<_>>(), keys);
+}
+
+#[test]
+fn colors_looks_swapped() {
+    let mut colors = Colors {
+        fill_color: Some(Color::new(0x11, 0x22, 0x33)),
+        background_color: Some(Color::new(0xFF, 0xEE, 0xCC)),
+        ..Colors::default()
+    };
+    assert!(colors.looks_swapped());
+    colors.unswap();
+    assert_eq!(colors.fill_color, Some(Color::new(0xFF, 0xEE, 0xCC)));
+    assert_eq!(colors.background_color, Some(Color::new(0x11, 0x22, 0x33)));
+    assert!(!colors.looks_swapped());
+    assert!(!Colors::default().looks_swapped());
+}
+
+#[test]
+fn on_off_quirks() {
+    let options =
+        Options::from_ini("quirks.shift=on\nquirks.loadstore=OFF\nquirks.clip=On\n").unwrap();
+    assert_eq!(options.quirks.shift, Some(true));
+    assert_eq!(
+        options.quirks.load_store,
+        Some(LoadStoreMode::IncrementByXPlus1)
+    );
+    assert_eq!(options.quirks.clip, Some(ClipMode::ClipBoth));
+    assert!(Options::from_ini("quirks.shift=maybe\n").is_err());
+
+    let options: Options = json!({"shiftQuirks":"Off","vBlankQuirks":"on","clipQuirks":"off"})
+        .to_string()
+        .parse()
+        .unwrap();
+    assert_eq!(options.quirks.shift, Some(false));
+    assert_eq!(options.quirks.vblank, Some(true));
+    assert_eq!(options.quirks.clip, Some(ClipMode::WrapBoth));
+}
+
+#[test]
+fn font_none() {
+    let (small, big) = Font::None.get_font_data();
+    assert!(small.iter().all(|&byte| byte == 0));
+    assert_eq!(big, None);
+    assert_eq!(serde_json::to_value(Font::None).unwrap(), json!("none"));
+    assert_eq!("none".parse::<Font>().unwrap(), Font::None);
+    let options = Options::from_ini("core.font=none\n").unwrap();
+    assert_eq!(options.font_style, Font::None);
+    assert_eq!(Font::default(), Font::Octo);
+}
+
+#[test]
+fn quirks_profile_hash() {
+    let mut quirks = Quirks::default();
+    let hash = quirks.profile_hash();
+    quirks.shift = None;
+    quirks.clip = None;
+    assert_eq!(quirks.profile_hash(), hash);
+    quirks.shift = Some(true);
+    assert_ne!(quirks.profile_hash(), hash);
+    assert_ne!(
+        Quirks::from(Platform::Vip).profile_hash(),
+        Quirks::from(Platform::Schip).profile_hash()
+    );
+}
+
+#[test]
+fn legacy_buzzer_color_aliases() {
+    let options: Options = json!({"buzzer":"#FF0000","silence":"#000033"})
+        .to_string()
+        .parse()
+        .unwrap();
+    assert_eq!(options.colors.buzz_color, Some(Color::new(255, 0, 0)));
+    assert_eq!(options.colors.quiet_color, Some(Color::new(0, 0, 0x33)));
+    let colors: Colors = serde_json::from_value(json!({"buzzerColor":"#00FF00"})).unwrap();
+    assert_eq!(colors.buzz_color, Some(Color::new(0, 255, 0)));
+}
+
+#[test]
+fn colors_to_json() {
+    let none = Colors::from_planes([None; 4], None, None);
+    assert_eq!(none.to_json(), "{}");
+    let json: serde_json::Value = serde_json::from_str(&Colors::default().to_json()).unwrap();
+    let object = json.as_object().unwrap();
+    assert_eq!(object.len(), 6);
+    assert_eq!(object["backgroundColor"], json!("#000000"));
+}
+
+#[test]
+fn custom_font_round_trip() {
+    let mut options = Options::default();
+    options.custom_font = Some(CustomFont {
+        small: vec![0xF0, 0x90, 0x90, 0x90, 0xF0, 0x20, 0x60, 0x20, 0x20, 0x70],
+        big: Some(vec![0xFF; 10]),
+    });
+    let json = options.to_string();
+    assert!(json.contains(r#""customFont":{"small":"8JCQkPAgYCAgcA==","big":"/////////////w=="}"#));
+    let round_tripped: Options = json.parse().unwrap();
+    assert_eq!(round_tripped, options);
+
+    let (small, big) = round_tripped.get_font_data();
+    assert_eq!(
+        small[..10],
+        [0xF0, 0x90, 0x90, 0x90, 0xF0, 0x20, 0x60, 0x20, 0x20, 0x70]
+    );
+    assert!(small[10..].iter().all(|&byte| byte == 0));
+    assert_eq!(big, Some(vec![0xFF; 10]));
+    assert!(!options.semantically_eq(&Options::default()));
+}
+
+#[test]
+fn requires_high_resolution() {
+    assert!(Options::new(Platform::Schip).requires_high_resolution());
+    assert!(Options::new(Platform::XoChip).requires_high_resolution());
+    let mut vip = Options::new(Platform::Vip);
+    assert!(!vip.requires_high_resolution());
+    vip.font_big_style = Some(Font::Vip);
+    assert!(!vip.requires_high_resolution());
+    vip.font_big_style = Some(Font::Schip);
+    assert!(vip.requires_high_resolution());
+}
+
+#[test]
+fn null_quirks() {
+    let options: Options =
+        r#"{"shiftQuirks":null,"clipQuirks":null,"loresDXY0Quirks":null,"vBlankQuirks":true}"#
+            .parse()
+            .unwrap();
+    assert_eq!(options.quirks.shift, None);
+    assert_eq!(options.quirks.clip, None);
+    assert_eq!(options.quirks.lores_dxy0, None);
+    assert_eq!(options.quirks.vblank, Some(true));
+    let options: Options = r#"{"quirks":{"shift":null}}"#.parse().unwrap();
+    assert_eq!(options.quirks.shift, None);
+}
+
+#[test]
+fn colors_average() {
+    let colors = Colors {
+        fill_color: Some(Color::new(255, 0, 0)),
+        fill_color2: Some(Color::new(0, 255, 0)),
+        blend_color: Some(Color::new(0, 0, 255)),
+        background_color: None,
+        buzz_color: None,
+        quiet_color: None,
+        background_transparent: None,
+    };
+    assert_eq!(colors.average(), Color::new(85, 85, 85));
+    let none = Colors {
+        fill_color: None,
+        fill_color2: None,
+        blend_color: None,
+        ..colors
+    };
+    assert_eq!(none.average(), Color::default());
+}
+
+#[test]
+fn byte_order_mark() {
+    let json = "\u{feff}{\"tickrate\": 20,\r\n\"shiftQuirks\": true}\r\n";
+    let options: Options = json.parse().unwrap();
+    assert_eq!(options.tickrate, Some(20));
+    assert_eq!(options.quirks.shift, Some(true));
+    assert_eq!(
+        Options::try_from_cart_bytes(json.as_bytes()).unwrap(),
+        options
+    );
+    assert_eq!(
+        Options::from_json_reader_limited(json.as_bytes(), 1000).unwrap(),
+        options
+    );
+    let ini = "\u{feff}core.tickrate = 20\r\n";
+    assert_eq!(Options::from_ini(ini).unwrap().tickrate, Some(20));
+}
+
+#[test]
+fn load_store_mode() {
+    let options: Options = r#"{"loadStoreQuirks":0}"#.parse().unwrap();
+    assert_eq!(
+        options.quirks.load_store,
+        Some(LoadStoreMode::IncrementByXPlus1)
+    );
+    let options: Options = r#"{"loadStoreQuirks":1}"#.parse().unwrap();
+    assert_eq!(options.quirks.load_store, Some(LoadStoreMode::Unchanged));
+
+    let mut options = Options::default();
+    options.quirks.load_store = Some(LoadStoreMode::IncrementByX);
+    let json = serde_json::to_value(&options).unwrap();
+    assert!(json
+        .as_object()
+        .unwrap()
+        .values()
+        .any(|value| value == "increment_by_x"));
+    assert_eq!(options.to_string().parse::<Options>().unwrap(), options);
+    assert_eq!(
+        Options::from_ini(&options.clone().to_ini())
+            .unwrap()
+            .quirks
+            .load_store,
+        options.quirks.load_store
+    );
+    let bits = options.quirks.to_bitfield();
+    assert_eq!(Quirks::from_bitfield(bits), Some(options.quirks.clone()));
+    assert_eq!(
+        options.quirks.inverted().load_store,
+        Some(LoadStoreMode::IncrementByX)
+    );
+    assert_eq!(
+        options.quirks.resolve().load_store,
+        LoadStoreMode::IncrementByX
+    );
+}
+
+#[test]
+fn font_compare() {
+    let comparison = Font::compare(Font::Octo, Font::Schip);
+    assert!(comparison.small_identical);
+    assert_eq!(comparison.big_identical, Some(false));
+    assert!(comparison.differing_small.is_empty());
+
+    let comparison = Font::compare(Font::Octo, Font::Octo);
+    assert_eq!(comparison.big_identical, Some(true));
+
+    let comparison = Font::compare(Font::Octo, Font::Vip);
+    assert!(!comparison.small_identical);
+    assert_eq!(comparison.big_identical, None);
+    assert_eq!(
+        comparison.differing_small,
+        Font::glyph_diff(Font::Octo, Font::Vip)
+    );
+}
+
+#[test]
+fn nested_colors() {
+    let flat: Options = r##"{"fillColor":"#FFCC00","backgroundColor":"#996600"}"##
+        .parse()
+        .unwrap();
+    let nested: Options = r##"{"colors":{"fill":"#FFCC00","background":"#996600"}}"##
+        .parse()
+        .unwrap();
+    assert_eq!(nested.colors, flat.colors);
+    assert_eq!(nested.colors.fill_color, Some(Color::new(0xFF, 0xCC, 0x00)));
+
+    let both: Options =
+        r##"{"fillColor":"#FFFFFF","quietColor":"#000000","colors":{"fill":"#FFCC00"}}"##
+            .parse()
+            .unwrap();
+    assert_eq!(both.colors.fill_color, Some(Color::new(0xFF, 0xCC, 0x00)));
+    assert_eq!(both.colors.quiet_color, Some(Color::new(0, 0, 0)));
+}
+
+#[test]
+fn quirks_summary_flags() {
+    assert_eq!(
+        Quirks::default().summary_flags(),
+        "S-l-j-L-c-v-f-d+r+w-h-C-s-o-"
+    );
+    let mut quirks = Quirks::from(Platform::Schip);
+    quirks.shift = None;
+    quirks.clip = Some(ClipMode::ClipXWrapY);
+    let flags = quirks.summary_flags();
+    assert!(flags.starts_with("S?l+"));
+    assert!(flags.contains("c~"));
+    assert_eq!(flags.len(), 28);
+}
+
+#[test]
+fn json_for_octo_version() {
+    let mut options = Options::new(Platform::Schip);
+    options.quirks.overflow_i = Some(true);
+    options.start_address = Some(0x200);
+
+    let classic: serde_json::Value =
+        serde_json::from_str(&options.to_json_for_octo_version(OctoVersion::Classic)).unwrap();
+    let classic = classic.as_object().unwrap();
+    assert!(classic.contains_key("shiftQuirks"));
+    assert!(!classic.contains_key("overflowIQuirks"));
+    assert!(!classic.contains_key("logicQuirks"));
+    assert!(!classic.contains_key("fontStyle"));
+    assert!(!classic.contains_key("startAddress"));
+
+    let current: serde_json::Value =
+        serde_json::from_str(&options.to_json_for_octo_version(OctoVersion::Current)).unwrap();
+    let current = current.as_object().unwrap();
+    assert!(current.contains_key("logicQuirks"));
+    assert!(current.contains_key("fontStyle"));
+    assert!(!current.contains_key("overflowIQuirks"));
+}
+
+#[test]
+fn color_approx_eq() {
+    let color = Color::new(100, 150, 200);
+    let close = Color::new(101, 150, 199);
+    assert!(color.approx_eq(&close, 1));
+    assert!(!color.approx_eq(&close, 0));
+    assert!(color.approx_eq(&color, 0));
+    assert!(!color.approx_eq(&Color::new(100, 152, 200), 1));
+}
+
+#[test]
+fn eti660_preset() {
+    let options = Options::new(Platform::Eti660);
+    assert_eq!(options.start_address, Some(1536));
+    assert_eq!(options.effective_start_address(), 1536);
+    assert_eq!(options.font_style, Font::Eti660);
+    assert!(!options.requires_high_resolution());
+    assert_eq!(options.quirks, Quirks::from(Platform::Eti660));
+}